@@ -1 +1,2 @@
 pub mod animation;
+pub mod aseprite;
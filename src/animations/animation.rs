@@ -1,4 +1,7 @@
-use crate::components::{AnimationIndices, AnimationTimer, GameState};
+use crate::components::{
+    AnimationController, AnimationFinishedAction, AnimationStates, GameState, OnAnimationEvent,
+    OnAnimationFinished, ProjectileDeleteMe, SpriteAnimation,
+};
 use bevy::prelude::*;
 
 pub struct AnimationSimplePlugin;
@@ -6,28 +9,124 @@ pub struct AnimationSimplePlugin;
 impl Plugin for AnimationSimplePlugin {
     fn build(&self, app: &mut App) {
         // basic animation
-        app.add_systems(Update, animate_sprite.run_if(in_state(GameState::Gameplay)));
+        app.add_systems(
+            Update,
+            (
+                (update_animation_controller, animate_sprite_clips).chain(),
+                animation_finished_action,
+            )
+                .run_if(in_state(GameState::Gameplay)),
+        );
     }
 }
 
-fn animate_sprite(
+// Turns the state asked by gameplay into a clip,
+// states without a clip in the sheet are skipped
+fn update_animation_controller(mut query: Query<(&mut AnimationController, &mut SpriteAnimation)>) {
+    for (mut controller, mut animation) in &mut query {
+        let trigger = controller.trigger.take();
+        if controller.is_dead() {
+            continue;
+        }
+
+        if controller.state.is_one_shot() && animation.is_finished() {
+            controller.state = controller.locomotion();
+        }
+
+        if let Some(trigger) = trigger {
+            let is_playing = controller.state == trigger && !animation.is_finished();
+            if trigger.priority() >= controller.state.priority() && !is_playing {
+                if animation.restart(trigger.clip()) {
+                    controller.state = trigger;
+                } else if trigger == AnimationStates::Die {
+                    // no die clip, stay on the current frame
+                    animation.timer.pause();
+                    controller.state = trigger;
+                }
+            }
+        }
+
+        if !controller.state.is_one_shot() {
+            let locomotion = controller.locomotion();
+            // sheets without an idle clip keep running
+            if !animation.play(locomotion.clip()) {
+                animation.play(AnimationStates::Run.clip());
+            }
+            controller.state = locomotion;
+        }
+    }
+}
+
+// Clips imported from Aseprite, each frame keeps its own duration
+fn animate_sprite_clips(
     time: Res<Time>,
-    mut query: Query<(&AnimationIndices, &mut AnimationTimer, &mut TextureAtlas)>,
+    mut query: Query<(Entity, &mut SpriteAnimation, &mut TextureAtlas)>,
+    mut animation_event: EventWriter<OnAnimationEvent>,
+    mut animation_finished: EventWriter<OnAnimationFinished>,
 ) {
-    for (indices, mut timer, mut atlas) in &mut query {
-        timer.tick(time.delta());
-        if timer.just_finished() {
-            if indices.is_repeating {
-                atlas.index = if atlas.index == indices.last {
-                    indices.first
-                } else {
-                    atlas.index + 1
-                };
+    for (entity, mut animation, mut atlas) in &mut query {
+        if animation.is_frame_entered {
+            animation.is_frame_entered = false;
+            let index = animation.index();
+            if let Some(clip) = animation.current_clip() {
+                for (_, name) in clip.events.iter().filter(|(frame, _)| *frame == index) {
+                    animation_event.send(OnAnimationEvent {
+                        entity,
+                        clip: animation.clip.clone(),
+                        name: name.clone(),
+                    });
+                }
+            }
+        }
+
+        animation.timer.tick(time.delta());
+        if animation.timer.just_finished() {
+            let Some(clip) = animation.current_clip() else {
+                continue;
+            };
+            let frame_count = clip.frames.len();
+            let is_repeating = clip.is_repeating;
+
+            if animation.frame + 1 < frame_count || is_repeating {
+                let frame = (animation.frame + 1) % frame_count;
+                let duration = animation.current_clip().unwrap().durations[frame];
+                animation.frame = frame;
+                animation.timer = Timer::from_seconds(duration, TimerMode::Once);
+                animation.is_frame_entered = true;
             } else {
-                if atlas.index < indices.last {
-                    atlas.index = atlas.index + 1;
+                animation_finished.send(OnAnimationFinished {
+                    entity,
+                    clip: animation.clip.clone(),
+                });
+                if let Some(next_clip) = animation.next_clip.take() {
+                    animation.play(&next_clip);
                 }
             }
         }
+
+        let index = animation.index();
+        if atlas.index != index {
+            atlas.index = index;
+        }
+    }
+}
+
+fn animation_finished_action(
+    mut commands: Commands,
+    mut animation_finished: EventReader<OnAnimationFinished>,
+    actions: Query<&AnimationFinishedAction>,
+) {
+    for event in animation_finished.read() {
+        let Ok(action) = actions.get(event.entity) else {
+            continue;
+        };
+        match action {
+            AnimationFinishedAction::Despawn => {
+                commands.entity(event.entity).despawn_recursive();
+            }
+            AnimationFinishedAction::DeleteProjectile => {
+                commands.entity(event.entity).insert(ProjectileDeleteMe);
+            }
+        }
     }
 }
@@ -0,0 +1,173 @@
+use crate::components::{AnimationClip, AnimationSheet};
+use bevy::prelude::*;
+use serde::Deserialize;
+use std::collections::HashMap;
+use std::fs;
+use std::path::Path;
+
+// Only the part of the Aseprite json export the game needs, exported with
+// `aseprite -b file.aseprite --sheet file.png --data file.json --format json-array --list-tags`
+// Paths are relative to the assets folder like every texture path
+
+#[derive(Deserialize, Debug)]
+pub struct AsepriteSheet {
+    pub frames: Vec<AsepriteFrame>,
+    pub meta: AsepriteMeta,
+}
+
+#[derive(Deserialize, Debug)]
+pub struct AsepriteFrame {
+    pub frame: AsepriteRect,
+    // milliseconds
+    pub duration: u32,
+}
+
+#[derive(Deserialize, Debug)]
+pub struct AsepriteRect {
+    pub x: u32,
+    pub y: u32,
+    pub w: u32,
+    pub h: u32,
+}
+
+#[derive(Deserialize, Debug)]
+#[serde(rename_all = "camelCase")]
+pub struct AsepriteMeta {
+    // sheet file, relative to the json
+    pub image: String,
+    pub size: AsepriteSize,
+    #[serde(default)]
+    pub frame_tags: Vec<AsepriteTag>,
+}
+
+#[derive(Deserialize, Debug)]
+pub struct AsepriteSize {
+    pub w: u32,
+    pub h: u32,
+}
+
+#[derive(Deserialize, Debug)]
+pub struct AsepriteTag {
+    pub name: String,
+    pub from: usize,
+    pub to: usize,
+    // forward, reverse, pingpong or pingpong_reverse
+    pub direction: String,
+    // missing when the tag loops forever
+    #[serde(default)]
+    pub repeat: Option<String>,
+    // user data of the tag, frame events like "1:hitbox, 3:sound"
+    // with the frame counted from the start of the tag
+    #[serde(default)]
+    pub data: Option<String>,
+}
+
+impl AsepriteTag {
+    fn frames(&self) -> Vec<usize> {
+        let forward: Vec<usize> = (self.from..=self.to).collect();
+        let reverse: Vec<usize> = forward.iter().rev().copied().collect();
+        // ends are not played twice in a row
+        let inner = forward.len().saturating_sub(2);
+        let once = match self.direction.as_str() {
+            "reverse" => reverse,
+            "pingpong" => forward
+                .iter()
+                .chain(reverse.iter().skip(1).take(inner))
+                .copied()
+                .collect(),
+            "pingpong_reverse" => reverse
+                .iter()
+                .chain(forward.iter().skip(1).take(inner))
+                .copied()
+                .collect(),
+            _ => forward,
+        };
+
+        once.repeat(self.repeat_count().unwrap_or(1) as usize)
+    }
+
+    fn events(&self) -> Vec<(usize, String)> {
+        let Some(data) = &self.data else {
+            return Vec::new();
+        };
+        data.split(',')
+            .filter_map(|event| {
+                let (frame, name) = event.split_once(':')?;
+                let frame = frame.trim().parse::<usize>().ok()?;
+                Some((self.from + frame, name.trim().to_string()))
+            })
+            .collect()
+    }
+
+    fn repeat_count(&self) -> Option<u32> {
+        self.repeat
+            .as_ref()
+            .and_then(|repeat| repeat.parse::<u32>().ok())
+            .filter(|repeat| *repeat > 0)
+    }
+}
+
+impl AsepriteSheet {
+    pub fn load(path: &str) -> AsepriteSheet {
+        let data = fs::read_to_string(Path::new("assets").join(path)).unwrap();
+        serde_json::from_str(&data).unwrap()
+    }
+
+    pub fn to_animation_sheet(
+        &self,
+        path: &str,
+        layouts: &mut Assets<TextureAtlasLayout>,
+    ) -> AnimationSheet {
+        let mut layout = TextureAtlasLayout::new_empty(Vec2::new(
+            self.meta.size.w as f32,
+            self.meta.size.h as f32,
+        ));
+        for frame in self.frames.iter() {
+            let min = Vec2::new(frame.frame.x as f32, frame.frame.y as f32);
+            let size = Vec2::new(frame.frame.w as f32, frame.frame.h as f32);
+            layout.add_texture(Rect::from_corners(min, min + size));
+        }
+
+        let durations: Vec<f32> = self
+            .frames
+            .iter()
+            .map(|frame| frame.duration as f32 / 1000.0)
+            .collect();
+        let clip = |frames: Vec<usize>, is_repeating: bool, events| AnimationClip {
+            durations: frames.iter().map(|frame| durations[*frame]).collect(),
+            frames,
+            is_repeating,
+            events,
+        };
+
+        // a sheet without tags plays all its frames as the default clip
+        let mut clips: HashMap<String, AnimationClip> = self
+            .meta
+            .frame_tags
+            .iter()
+            .map(|tag| {
+                (
+                    tag.name.clone(),
+                    clip(tag.frames(), tag.repeat_count().is_none(), tag.events()),
+                )
+            })
+            .collect();
+        if clips.is_empty() {
+            clips.insert(
+                "default".to_string(),
+                clip((0..self.frames.len()).collect(), true, Vec::new()),
+            );
+        }
+
+        let texture_path = Path::new(path)
+            .parent()
+            .unwrap_or(Path::new(""))
+            .join(&self.meta.image);
+
+        AnimationSheet {
+            texture_path: texture_path.to_string_lossy().to_string(),
+            layout: layouts.add(layout),
+            clips,
+        }
+    }
+}
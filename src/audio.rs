@@ -0,0 +1,2 @@
+pub mod music;
+pub mod sound;
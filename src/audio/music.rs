@@ -0,0 +1,83 @@
+use crate::components::*;
+use bevy::audio::Volume;
+use bevy::prelude::*;
+
+pub struct MusicPlugin;
+
+impl Plugin for MusicPlugin {
+    fn build(&self, app: &mut App) {
+        app.add_systems(Update, (update_music_track, crossfade_music).chain());
+    }
+}
+
+// Menus share a track, a run plays the music of its stage
+fn update_music_track(
+    mut commands: Commands,
+    asset_server: Res<AssetServer>,
+    state: Res<State<GameState>>,
+    audio_data: Res<AudioData>,
+    stages: Res<StagesResource>,
+    selected_stage: Res<SelectedStage>,
+    mut tracks: Query<&mut MusicTrack>,
+) {
+    let wanted_path = match state.get() {
+        GameState::MainMenu
+        | GameState::StageSelect
+        | GameState::Settings
+        | GameState::GameOver => Some(audio_data.menu_music.clone()),
+        _ => stages
+            .stages
+            .get(selected_stage.0)
+            .and_then(|stage| stage.music_path.clone()),
+    };
+
+    let mut is_playing = false;
+    for mut track in &mut tracks {
+        let is_wanted = Some(&track.path) == wanted_path.as_ref();
+        // coming back to a track fading out fades it in again
+        track.is_fading_out = !is_wanted;
+        is_playing |= is_wanted;
+    }
+
+    if let (false, Some(path)) = (is_playing, wanted_path) {
+        commands.spawn((
+            AudioBundle {
+                source: asset_server.load(&path),
+                // silent until crossfade_music fades it in
+                settings: PlaybackSettings::LOOP.with_volume(Volume::new(0.0)),
+            },
+            MusicTrack {
+                path,
+                fade: 0.0,
+                is_fading_out: false,
+            },
+            Name::new("Music"),
+        ));
+    }
+}
+
+// Real time, the music keeps fading while the game is paused
+fn crossfade_music(
+    mut commands: Commands,
+    mut tracks: Query<(Entity, &mut MusicTrack, Option<&AudioSink>)>,
+    audio_data: Res<AudioData>,
+    volumes: Res<AudioVolumes>,
+    time: Res<Time<Real>>,
+) {
+    let step = time.delta_seconds() / audio_data.crossfade_duration.max(0.01);
+    for (entity, mut track, sink) in &mut tracks {
+        let fade = match track.is_fading_out {
+            true => track.fade - step,
+            false => track.fade + step,
+        };
+        track.fade = fade.clamp(0.0, 1.0);
+
+        if track.is_fading_out && track.fade <= 0.0 {
+            commands.entity(entity).despawn_recursive();
+            continue;
+        }
+        if let Some(sink) = sink {
+            sink.set_volume(track.fade * volumes.music * volumes.master);
+        }
+    }
+}
@@ -0,0 +1,128 @@
+use crate::components::*;
+use bevy::audio::Volume;
+use bevy::prelude::*;
+use std::collections::HashMap;
+use std::fs;
+
+pub struct SoundPlugin;
+
+impl Plugin for SoundPlugin {
+    fn build(&self, app: &mut App) {
+        let data = fs::read_to_string("assets/audio.ron").unwrap();
+        let audio_data: AudioData = ron::from_str(&data).unwrap();
+        app.insert_resource(audio_data);
+        app.init_resource::<AudioVolumes>();
+        app.init_resource::<SoundHandles>();
+
+        app.add_systems(Startup, load_sounds);
+        app.add_systems(OnEnter(GameState::PlayerLevelUp), level_up_sound);
+        app.add_systems(OnEnter(GameState::PlayerChooseWeapon), level_up_sound);
+        app.add_systems(
+            Update,
+            (
+                gameplay_sounds.run_if(in_state(GameState::Gameplay)),
+                play_sounds,
+            )
+                .chain(),
+        );
+    }
+}
+
+fn load_sounds(
+    asset_server: Res<AssetServer>,
+    audio_data: Res<AudioData>,
+    mut sound_handles: ResMut<SoundHandles>,
+) {
+    for (sound_type, sound) in audio_data.sounds.iter() {
+        sound_handles
+            .0
+            .insert(*sound_type, asset_server.load(&sound.path));
+    }
+}
+
+// Many hits in the same frame only ask for one sound
+fn gameplay_sounds(
+    mut enemy_hit: EventReader<OnEnemyHit>,
+    mut enemy_died: EventReader<OnEnemyDied>,
+    mut collect_experience: EventReader<OnCollectExperience>,
+    mut player_received_damage: EventReader<OnPlayerReceivedDamage>,
+    mut play_sound: EventWriter<OnPlaySound>,
+) {
+    let sounds = [
+        (enemy_hit.read().count(), SoundTypes::EnemyHit),
+        (enemy_died.read().count(), SoundTypes::EnemyDied),
+        (
+            collect_experience.read().count(),
+            SoundTypes::CollectExperience,
+        ),
+        (
+            player_received_damage.read().count(),
+            SoundTypes::PlayerHurt,
+        ),
+    ];
+    for (count, sound) in sounds {
+        if count > 0 {
+            play_sound.send(OnPlaySound { sound });
+        }
+    }
+}
+
+fn level_up_sound(mut play_sound: EventWriter<OnPlaySound>) {
+    play_sound.send(OnPlaySound {
+        sound: SoundTypes::LevelUp,
+    });
+}
+
+// Rate limited per sound so a big wave of hits does not clip
+fn play_sounds(
+    mut commands: Commands,
+    mut play_sound: EventReader<OnPlaySound>,
+    audio_data: Res<AudioData>,
+    sound_handles: Res<SoundHandles>,
+    audio_sources: Res<Assets<AudioSource>>,
+    volumes: Res<AudioVolumes>,
+    sound_effects: Query<&SoundEffect>,
+    time: Res<Time<Real>>,
+    mut last_played: Local<HashMap<SoundTypes, f32>>,
+) {
+    let mut playing: HashMap<SoundTypes, usize> = HashMap::new();
+    for sound_effect in &sound_effects {
+        *playing.entry(**sound_effect).or_default() += 1;
+    }
+
+    let now = time.elapsed_seconds();
+    for event in play_sound.read() {
+        let (Some(sound), Some(handle)) = (
+            audio_data.sounds.get(&event.sound),
+            sound_handles.0.get(&event.sound),
+        ) else {
+            continue;
+        };
+        // missing files never load, nothing would ever despawn the entity
+        if !audio_sources.contains(handle) {
+            continue;
+        }
+        if last_played
+            .get(&event.sound)
+            .is_some_and(|last_played| now - last_played < sound.min_interval)
+        {
+            continue;
+        }
+        let playing = playing.entry(event.sound).or_default();
+        if *playing >= sound.max_playing {
+            continue;
+        }
+
+        *playing += 1;
+        last_played.insert(event.sound, now);
+        commands.spawn((
+            AudioBundle {
+                source: handle.clone(),
+                settings: PlaybackSettings::DESPAWN
+                    .with_volume(Volume::new(sound.volume * volumes.sfx * volumes.master)),
+            },
+            SoundEffect(event.sound),
+            Name::new("Sound effect"),
+        ));
+    }
+}
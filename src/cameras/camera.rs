@@ -1,54 +1,216 @@
-use crate::components::Player;
+use crate::components::{
+    AnimationController, CameraController, DebugFlags, EnemyBossDrop, GameSettings, OnCameraShake,
+    OnPlayerReceivedDamage, Player, SelectedStage, StagesResource,
+};
 use bevy::input::mouse::MouseMotion;
 use bevy::prelude::*;
 use bevy_pixel_camera::{PixelViewport, PixelZoom};
 
-use crate::constants::{SCREEN_HEIGHT, SCREEN_WIDTH};
+use crate::constants::{
+    BOSS_SPAWN_TRAUMA, CAMERA_SCALE, DEBUG_ZOOM_MAX_SCALE, DEBUG_ZOOM_MIN_SCALE,
+    PLAYER_HIT_SHAKE_COOLDOWN, PLAYER_HIT_TRAUMA, SCREEN_HEIGHT, SCREEN_WIDTH, UI_REFERENCE_ZOOM,
+};
 use crate::players::player::player_movement;
 use bevy::input::mouse::MouseScrollUnit;
 use bevy::input::mouse::MouseWheel;
+use bevy::window::{PrimaryWindow, WindowResized};
+use rand::Rng;
+use std::env;
 
 pub struct PlayerCameraPlugin;
 
 impl Plugin for PlayerCameraPlugin {
     fn build(&self, app: &mut App) {
+        // debug flags can be set in a .env file
+        dotenvy::dotenv().ok();
+        app.insert_resource(DebugFlags {
+            is_debug_zoom: env::var("DEBUG_ZOOM").is_ok_and(|value| value == "1"),
+        });
         app.add_systems(Startup, setup_camera);
-        app.add_systems(Update, camera_follow.after(player_movement));
-        app.add_systems(Update, zoom_in);
+        app.add_systems(
+            Update,
+            (
+                (shake_on_player_hit, shake_on_boss_spawn),
+                add_camera_trauma,
+                camera_follow.after(player_movement),
+            )
+                .chain(),
+        );
+        app.add_systems(
+            Update,
+            zoom_in.run_if(|debug_flags: Res<DebugFlags>| debug_flags.is_debug_zoom),
+        );
+        app.add_systems(
+            Update,
+            update_ui_scale
+                .run_if(resource_changed::<GameSettings>.or_else(on_event::<WindowResized>())),
+        );
         // app.add_systems(Update, debug_camera);
     }
 }
 
-pub fn setup_camera(mut commands: Commands) {
-    commands.spawn((
+// Pixel perfect renders SCREEN_WIDTH x SCREEN_HEIGHT with an integer zoom,
+// black bars fill the rest of the window
+pub fn spawn_camera(commands: &mut Commands, is_pixel_perfect: bool) {
+    let mut camera = commands.spawn((
         Camera2dBundle {
             projection: OrthographicProjection {
-                scale: 0.5,
+                scale: match is_pixel_perfect {
+                    true => 1.0,
+                    false => CAMERA_SCALE,
+                },
                 far: 1000.,
                 near: -1000.,
                 ..default()
             },
             ..default()
         },
-        // PixelZoom::FitSize {
-        //     width: SCREEN_WIDTH,
-        //     height: SCREEN_HEIGHT,
-        //     // width: 4000,
-        //     // height: 3000,
-        // },
-        // PixelViewport,
+        CameraController::default(),
+        Name::new("Camera"),
     ));
+    if is_pixel_perfect {
+        camera.insert((
+            PixelZoom::FitSize {
+                width: SCREEN_WIDTH,
+                height: SCREEN_HEIGHT,
+            },
+            PixelViewport,
+        ));
+    }
+}
+
+fn setup_camera(mut commands: Commands, settings: Res<GameSettings>) {
+    spawn_camera(&mut commands, settings.is_pixel_perfect);
 }
 
 fn camera_follow(
-    player: Query<&Transform, (With<Player>, Without<Camera>)>,
-    mut camera: Query<&mut Transform, (With<Camera>, Without<Player>)>,
+    player: Query<(&Transform, &Player, &AnimationController), Without<Camera>>,
+    mut camera: Query<
+        (
+            &mut Transform,
+            &mut CameraController,
+            &OrthographicProjection,
+        ),
+        Without<Player>,
+    >,
+    settings: Res<GameSettings>,
+    stages: Res<StagesResource>,
+    selected_stage: Res<SelectedStage>,
+    time: Res<Time>,
+) {
+    let Ok((player_transform, player, animation_controller)) = player.get_single() else {
+        return;
+    };
+    let Ok((mut transform, mut controller, projection)) = camera.get_single_mut() else {
+        return;
+    };
+
+    let mut target = player_transform.translation.truncate();
+    if animation_controller.is_moving {
+        target += player.facing * controller.look_ahead;
+    }
+
+    // a new camera starts on the player instead of sliding from the origin
+    controller.position = match controller.smoothing > 0.0 && !controller.is_added() {
+        true => {
+            let t = 1.0 - (-controller.smoothing * time.delta_seconds()).exp();
+            controller.position.lerp(target, t)
+        }
+        false => target,
+    };
+
+    if let Some(bounds) = stages.stages[selected_stage.0].camera_bounds {
+        let half_size = projection.area.half_size();
+        let min = Vec2::new(bounds.min_x, bounds.min_y) + half_size;
+        let max = Vec2::new(bounds.max_x, bounds.max_y) - half_size;
+        // stage smaller than the view, keep it centered
+        let center = (min + max) / 2.0;
+        controller.position = Vec2::new(
+            match min.x < max.x {
+                true => controller.position.x.clamp(min.x, max.x),
+                false => center.x,
+            },
+            match min.y < max.y {
+                true => controller.position.y.clamp(min.y, max.y),
+                false => center.y,
+            },
+        );
+    }
+
+    let mut position = controller.position;
+    if controller.trauma > 0.0 {
+        let mut rng = rand::thread_rng();
+        let shake = controller.trauma.powi(2) * settings.screen_shake;
+        position += Vec2::new(rng.gen_range(-1.0..1.0), rng.gen_range(-1.0..1.0))
+            * controller.max_shake_offset
+            * shake;
+        controller.trauma =
+            (controller.trauma - controller.trauma_decay * time.delta_seconds()).max(0.0);
+    }
+
+    transform.translation.x = position.x;
+    transform.translation.y = position.y;
+    // whole pixels only, sprites would shimmer otherwise
+    if settings.is_pixel_perfect {
+        transform.translation = transform.translation.round();
+    }
+}
+
+fn add_camera_trauma(
+    mut camera_shake_events: EventReader<OnCameraShake>,
+    mut camera: Query<&mut CameraController>,
+) {
+    for event in camera_shake_events.read() {
+        for mut controller in &mut camera {
+            controller.trauma = (controller.trauma + event.trauma).min(1.0);
+        }
+    }
+}
+
+// Contact damage comes every frame, so only shake once in a while
+fn shake_on_player_hit(
+    mut player_received_damage: EventReader<OnPlayerReceivedDamage>,
+    mut camera_shake_event: EventWriter<OnCameraShake>,
+    time: Res<Time>,
+    mut cooldown: Local<f32>,
+) {
+    *cooldown -= time.delta_seconds();
+    if player_received_damage.read().count() > 0 && *cooldown <= 0.0 {
+        camera_shake_event.send(OnCameraShake {
+            trauma: PLAYER_HIT_TRAUMA,
+        });
+        *cooldown = PLAYER_HIT_SHAKE_COOLDOWN;
+    }
+}
+
+// Only bosses drop a boss chest
+fn shake_on_boss_spawn(
+    bosses: Query<(), Added<EnemyBossDrop>>,
+    mut camera_shake_event: EventWriter<OnCameraShake>,
+) {
+    if !bosses.is_empty() {
+        camera_shake_event.send(OnCameraShake {
+            trauma: BOSS_SPAWN_TRAUMA,
+        });
+    }
+}
+
+// The ui is laid out for a window UI_REFERENCE_ZOOM times the game resolution
+fn update_ui_scale(
+    settings: Res<GameSettings>,
+    windows: Query<&Window, With<PrimaryWindow>>,
+    mut ui_scale: ResMut<UiScale>,
 ) {
-    if let Ok(player) = player.get_single() {
-        let mut camera = camera.single_mut();
-        camera.translation.x = player.translation.x;
-        camera.translation.y = player.translation.y;
+    let Ok(window) = windows.get_single() else {
+        return;
+    };
+    let mut zoom =
+        (window.width() / SCREEN_WIDTH as f32).min(window.height() / SCREEN_HEIGHT as f32);
+    // same zoom as PixelZoom::FitSize so the ui matches the world
+    if settings.is_pixel_perfect {
+        zoom = zoom.floor().max(1.0);
     }
+    ui_scale.0 = settings.ui_scale * zoom / UI_REFERENCE_ZOOM;
 }
 
 // fn debug_camera(
@@ -83,5 +245,7 @@ pub fn zoom_in(
     }
     let mut log_scale = projection.scale.ln();
     log_scale -= delta * time.delta_seconds();
-    projection.scale = log_scale.exp();
+    projection.scale = log_scale
+        .exp()
+        .clamp(DEBUG_ZOOM_MIN_SCALE, DEBUG_ZOOM_MAX_SCALE);
 }
@@ -3,8 +3,10 @@ use bevy::math::Vec2;
 use bevy::prelude::*;
 use bevy::time::Stopwatch;
 use bevy_rapier2d::prelude::*;
+use rand::Rng;
 use serde::{Deserialize, Serialize};
 use std::collections::HashMap;
+use std::sync::Arc;
 
 #[derive(Debug, Clone, Copy, Default, Eq, PartialEq, Hash, States)]
 pub enum GameState {
@@ -15,6 +17,9 @@ pub enum GameState {
     PlayerLevelUp,
     PlayerUpdateWeapon,
     PlayerChooseWeapon,
+    Paused,
+    StageSelect,
+    Settings,
 }
 
 // PLAYER
@@ -25,9 +30,36 @@ pub enum GameState {
 
 #[derive(Component)]
 pub struct Player {
-    pub facing: Facing,
+    // last movement direction, one of the eight directions and never zero
+    pub facing: Vec2,
+    // mouse or right stick direction, None when weapons aim on their own
+    pub aim: Option<Vec2>,
 }
 
+impl Player {
+    // where directional weapons shoot
+    pub fn aim_direction(&self) -> Vec2 {
+        self.aim.unwrap_or(self.facing)
+    }
+}
+
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub enum AimMode {
+    // closest enemy or facing direction
+    #[default]
+    Auto,
+    // mouse cursor or right stick
+    Manual,
+}
+
+impl AimMode {
+    pub const ALL: [AimMode; 2] = [AimMode::Auto, AimMode::Manual];
+}
+
+// Set from the main menu, kept for the whole run
+#[derive(Resource, Default)]
+pub struct SelectedAimMode(pub AimMode);
+
 #[derive(Debug, Deserialize, Serialize)]
 pub struct PlayerStats {
     pub mul_max_health: f32,
@@ -46,12 +78,23 @@ pub struct PlayerStats {
     pub mul_curse: f32,
     pub mul_magnet: f32,
     pub add_extra_life: u32, // TODO use value
+    pub add_crit_chance: f32,
+    pub add_crit_multiplier: f32,
 }
 
 #[derive(Resource, Debug, Deserialize, Serialize)]
 pub struct PlayerMetaStats {
     pub data: PlayerStats,
     pub gold: u32,
+    // names of the stages survived at least once, used to unlock stages
+    pub survived_stages: Vec<String>,
+}
+
+// What is kept between runs, saved in a ron file
+#[derive(Debug, Default, Deserialize, Serialize)]
+pub struct MetaProgressionSave {
+    pub gold: u32,
+    pub survived_stages: Vec<String>,
 }
 
 // Will be set by a ron file for each character
@@ -77,6 +120,8 @@ pub struct PlayerInGameStats {
     pub curse: f32,
     pub magnet: f32,
     pub extra_life: u32,
+    pub crit_chance: f32,
+    pub crit_multiplier: f32,
 }
 // Default value for all character before multiplication
 impl Default for PlayerInGameStats {
@@ -98,6 +143,8 @@ impl Default for PlayerInGameStats {
             curse: BASE_CURSE,
             magnet: BASE_MAGNET,
             extra_life: BASE_EXTRA_LIFE,
+            crit_chance: BASE_CRIT_CHANCE,
+            crit_multiplier: BASE_CRIT_MULTIPLIER,
         }
     }
 }
@@ -131,6 +178,9 @@ pub const BASE_CURSE: f32 = 1.0;
 
 pub const BASE_EXTRA_LIFE: u32 = 0;
 
+pub const BASE_CRIT_CHANCE: f32 = 0.05; // 5% chance, additive
+pub const BASE_CRIT_MULTIPLIER: f32 = 1.5; // critical hits deal 150% damage
+
 #[derive(Debug, PartialEq, Clone, Copy, Deserialize, Serialize)]
 pub enum PlayerBaseStatsType {
     MaxHealth,
@@ -149,6 +199,8 @@ pub enum PlayerBaseStatsType {
     Greed,
     Curse,
     ExtraLife,
+    CritChance,
+    CritMultiplier,
 }
 
 #[derive(Resource, Debug)]
@@ -189,16 +241,28 @@ pub struct EnemiesResource {
     pub rabbit: EnemyData,
     pub skull: EnemyData,
     pub boss_wolf: EnemyData,
+    pub reaper: EnemyData,
+}
+
+impl EnemiesResource {
+    pub fn get(&self, enemy_type: EnemyTypes) -> &EnemyData {
+        match enemy_type {
+            EnemyTypes::Bat => &self.bat,
+            EnemyTypes::Bee => &self.bee,
+            EnemyTypes::Golem => &self.golem,
+            EnemyTypes::Rabbit => &self.rabbit,
+            EnemyTypes::Skull => &self.skull,
+            EnemyTypes::BossWolf => &self.boss_wolf,
+            EnemyTypes::Reaper => &self.reaper,
+        }
+    }
 }
 
 #[derive(Debug, Deserialize, Serialize)]
 pub struct EnemyData {
-    pub texture_path: String,
+    // Aseprite json export, the "run" clip is played while alive
+    pub animation_path: String,
     pub texture_shadow_path: String,
-    pub texture_layout_size: Vec2,
-    pub texture_layout_columns: usize,
-    pub texture_layout_rows: usize,
-    pub animation_last_indice: usize,
     pub health: f32,
     pub speed: f32,
     pub damage: f32,
@@ -208,12 +272,39 @@ pub struct EnemyData {
     pub experience_drop: u32, // bosses has an experience drop of 0
     pub is_boss: bool,
     pub is_semi_boss: bool,
+    #[serde(default)]
+    pub immunities: Vec<StatusEffectTypes>,
+    // percentage of damage ignored per damage type, negative value is a weakness
+    #[serde(default)]
+    pub resistances: HashMap<DamageTypes, f32>,
+    #[serde(default)]
+    pub drops: Vec<PickupDrop>,
+    // ignores damage, knockback and is never despawned
+    #[serde(default)]
+    pub is_unkillable: bool,
+    // max alive at the same time, extra spawns bring back far away ones instead
+    #[serde(default = "default_population_budget")]
+    pub population_budget: u32,
+    // smoke played where the enemy died
+    #[serde(default)]
+    pub death_effect: Option<DeathEffectData>,
+}
+
+#[derive(Debug, Deserialize, Serialize)]
+pub struct DeathEffectData {
+    pub animation_path: String,
+    pub clip: String,
+    pub scale: f32,
+}
+
+fn default_population_budget() -> u32 {
+    200
 }
 
 #[derive(Component)]
 pub struct Enemy;
 
-#[derive(Debug, PartialEq, Clone, Copy)]
+#[derive(Debug, PartialEq, Eq, Hash, Clone, Copy, Deserialize, Serialize)]
 pub enum EnemyTypes {
     Bat,
     Bee,
@@ -221,14 +312,22 @@ pub enum EnemyTypes {
     Rabbit,
     Skull,
     BossWolf,
+    Reaper,
 }
 
+#[derive(Component, Deref)]
+pub struct EnemyType(pub EnemyTypes);
+
 #[derive(Component, Deref, DerefMut)]
 pub struct EnemyVelocity(pub Vec2);
 
 #[derive(Component, Deref, DerefMut)]
 pub struct EnemySpeed(pub f32);
 
+// Walk in a straight line, used by walls and swarms crossing the screen
+#[derive(Component, Deref)]
+pub struct EnemyFixedDirection(pub Vec2);
+
 #[derive(Component, Deref, DerefMut)]
 pub struct EnemyDamageOverTime(pub f32);
 
@@ -238,25 +337,244 @@ pub struct EnemyExperienceDrop(pub u32);
 #[derive(Component)]
 pub struct EnemyBossDrop;
 
+#[derive(Component)]
+pub struct EnemyUnkillable;
+
+// Health reached 0, the enemy fades away without collider before being despawned
+#[derive(Component)]
+pub struct EnemyDying {
+    pub timer: Timer,
+}
+
+#[derive(Component, Deref, DerefMut)]
+pub struct EnemyHitFlash(pub Timer);
+
+// the reaper ignores damage, knockback, bombs and despawn distance
+pub type KillableEnemy = (With<Enemy>, Without<EnemyUnkillable>);
+
+#[derive(Component, Deref, DerefMut)]
+pub struct EnemyResistances(pub HashMap<DamageTypes, f32>);
+
+#[derive(Component, Deref)]
+pub struct EnemyDrops(pub Vec<PickupDrop>);
+
+// Alive enemies per type, recounted every frame before spawning
+#[derive(Resource, Default)]
+pub struct EnemyPopulation {
+    pub alive: HashMap<EnemyTypes, u32>,
+    pub total: u32,
+    // spawns refused because of the budget, as many far away enemies get recycled
+    pub overflow: HashMap<EnemyTypes, u32>,
+}
+
+impl EnemyPopulation {
+    // false when the type or the whole population is full
+    pub fn try_add(&mut self, enemy_type: EnemyTypes, budget: u32) -> bool {
+        let alive = self.alive.entry(enemy_type).or_default();
+        if *alive >= budget || self.total >= MAX_ENEMIES {
+            *self.overflow.entry(enemy_type).or_default() += 1;
+            return false;
+        }
+        *alive += 1;
+        self.total += 1;
+        true
+    }
+
+    // bosses are always spawned
+    pub fn add(&mut self, enemy_type: EnemyTypes) {
+        *self.alive.entry(enemy_type).or_default() += 1;
+        self.total += 1;
+    }
+}
+
 // ###################################################################
 // ###################################################################
 // ###################################################################
 // ###################################################################
 // Animation
 
-#[derive(Component)]
-pub struct AnimationIndices {
-    pub first: usize,
-    pub last: usize,
+// Sprite sheet with its named clips, imported from an Aseprite json export
+#[derive(Debug)]
+pub struct AnimationSheet {
+    pub texture_path: String,
+    pub layout: Handle<TextureAtlasLayout>,
+    pub clips: HashMap<String, AnimationClip>,
+}
+
+// Atlas indices in play order, direction and repeat count of the tag are already applied
+#[derive(Debug)]
+pub struct AnimationClip {
+    pub frames: Vec<usize>,
+    // seconds, one per frame
+    pub durations: Vec<f32>,
     pub is_repeating: bool,
+    // atlas index and name, sent as OnAnimationEvent every time the frame shows up
+    pub events: Vec<(usize, String)>,
 }
 
-#[derive(Component, Deref, DerefMut)]
-pub struct AnimationTimer(pub Timer);
+// Plays the clips of a sheet with the duration of each frame
+#[derive(Component)]
+pub struct SpriteAnimation {
+    pub sheet: Arc<AnimationSheet>,
+    pub clip: String,
+    pub frame: usize,
+    pub timer: Timer,
+    // played once a clip that does not repeat is over
+    pub next_clip: Option<String>,
+    // the events of the current frame are not sent yet
+    pub is_frame_entered: bool,
+}
+
+impl SpriteAnimation {
+    pub fn new(sheet: Arc<AnimationSheet>, clip: &str) -> Self {
+        let mut animation = Self {
+            sheet,
+            clip: String::new(),
+            frame: 0,
+            timer: Timer::default(),
+            next_clip: None,
+            is_frame_entered: false,
+        };
+        animation.play(clip);
+        animation
+    }
+
+    // Restarts only when switching clip, false if the sheet has no such clip
+    pub fn play(&mut self, clip: &str) -> bool {
+        let Some(duration) = self.sheet.clips.get(clip).map(|clip| clip.durations[0]) else {
+            return false;
+        };
+        self.next_clip = None;
+        if self.clip != clip {
+            self.clip = clip.to_string();
+            self.frame = 0;
+            self.timer = Timer::from_seconds(duration, TimerMode::Once);
+            self.is_frame_entered = true;
+        }
+        true
+    }
+
+    // Same as play but starts over when the clip is already playing
+    pub fn restart(&mut self, clip: &str) -> bool {
+        if self.clip == clip {
+            self.clip.clear();
+        }
+        self.play(clip)
+    }
+
+    // One shot clip like a hit, then back to another clip
+    pub fn play_once(&mut self, clip: &str, then: &str) -> bool {
+        let is_playing = self.restart(clip);
+        if is_playing {
+            self.next_clip = Some(then.to_string());
+        }
+        is_playing
+    }
+
+    pub fn current_clip(&self) -> Option<&AnimationClip> {
+        self.sheet.clips.get(&self.clip)
+    }
+
+    pub fn index(&self) -> usize {
+        self.current_clip()
+            .map(|clip| clip.frames[self.frame])
+            .unwrap_or_default()
+    }
+
+    pub fn is_finished(&self) -> bool {
+        self.current_clip().is_some_and(|clip| {
+            !clip.is_repeating && self.frame == clip.frames.len() - 1 && self.timer.finished()
+        })
+    }
+}
+
+// What a sprite is doing, each state plays the clip of the same name
+#[derive(Debug, Default, Eq, PartialEq, Clone, Copy)]
+pub enum AnimationStates {
+    #[default]
+    Idle,
+    Run,
+    Attack,
+    Hurt,
+    Die,
+}
+
+impl AnimationStates {
+    pub fn clip(&self) -> &'static str {
+        match self {
+            AnimationStates::Idle => "idle",
+            AnimationStates::Run => "run",
+            AnimationStates::Attack => "attack",
+            AnimationStates::Hurt => "hurt",
+            AnimationStates::Die => "die",
+        }
+    }
+
+    // go back to idle or run once their clip is over, die stays on its last frame
+    pub fn is_one_shot(&self) -> bool {
+        matches!(
+            self,
+            AnimationStates::Attack | AnimationStates::Hurt | AnimationStates::Die
+        )
+    }
+
+    // a state is not interrupted by a lower one
+    pub fn priority(&self) -> u8 {
+        match self {
+            AnimationStates::Idle | AnimationStates::Run => 0,
+            AnimationStates::Attack => 1,
+            AnimationStates::Hurt => 2,
+            AnimationStates::Die => 3,
+        }
+    }
+}
+
+// State machine on top of a SpriteAnimation, gameplay only says what happens
+// and the controller picks the clip, see update_animation_controller
+#[derive(Component, Default)]
+pub struct AnimationController {
+    pub state: AnimationStates,
+    // run or idle when no one shot state is playing
+    pub is_moving: bool,
+    // one shot state asked this frame
+    pub trigger: Option<AnimationStates>,
+}
+
+impl AnimationController {
+    pub fn moving() -> Self {
+        Self {
+            state: AnimationStates::Run,
+            is_moving: true,
+            trigger: None,
+        }
+    }
+
+    pub fn trigger(&mut self, state: AnimationStates) {
+        // the strongest state asked during the frame wins
+        match self.trigger {
+            Some(trigger) if trigger.priority() > state.priority() => {}
+            _ => self.trigger = Some(state),
+        }
+    }
 
-pub enum Facing {
-    Left,
-    Right,
+    pub fn locomotion(&self) -> AnimationStates {
+        match self.is_moving {
+            true => AnimationStates::Run,
+            false => AnimationStates::Idle,
+        }
+    }
+
+    pub fn is_dead(&self) -> bool {
+        self.state == AnimationStates::Die
+    }
+}
+
+// One shot callback once the clip that does not repeat is over
+#[derive(Component, Clone, Copy)]
+pub enum AnimationFinishedAction {
+    Despawn,
+    // goes through projectile_delete like any other deleted projectile
+    DeleteProjectile,
 }
 
 // ###################################################################
@@ -265,7 +583,7 @@ pub enum Facing {
 // ###################################################################
 // WEAPONS
 
-#[derive(Debug, Eq, PartialEq, Clone, Copy)]
+#[derive(Debug, Eq, PartialEq, Hash, Clone, Copy, Serialize)]
 pub enum WeaponsTypes {
     Claw,
     FireArea,
@@ -391,7 +709,7 @@ impl WeaponsUpgradesTypes {
         }
     }
 }
-#[derive(Debug, Eq, PartialEq, Clone, Copy)]
+#[derive(Debug, Eq, PartialEq, Hash, Clone, Copy, Serialize)]
 pub enum ProjectileTypes {
     Claw,
     FireArea,
@@ -406,6 +724,77 @@ pub enum ProjectileTypes {
     BouncingBallSplit,
     FireBoots,
     LightSwords,
+    // damage over time from status effects
+    Burn,
+    Poison,
+    // bomb pickup, damage is the health taken away
+    Bomb,
+}
+
+impl ProjectileTypes {
+    pub fn damage_type(&self) -> DamageTypes {
+        match self {
+            ProjectileTypes::Claw => DamageTypes::Physical,
+            ProjectileTypes::FireArea => DamageTypes::Fire,
+            ProjectileTypes::ArcaneMissile => DamageTypes::Arcane,
+            ProjectileTypes::ArcaneMissileSplit => DamageTypes::Arcane,
+            ProjectileTypes::ArcaneMissileExplosion => DamageTypes::Arcane,
+            ProjectileTypes::Shuriken => DamageTypes::Physical,
+            ProjectileTypes::ShurikenMini => DamageTypes::Physical,
+            ProjectileTypes::ChainLightning => DamageTypes::Lightning,
+            ProjectileTypes::SlowDome => DamageTypes::Arcane,
+            ProjectileTypes::BouncingBall => DamageTypes::Physical,
+            ProjectileTypes::BouncingBallSplit => DamageTypes::Physical,
+            ProjectileTypes::FireBoots => DamageTypes::Fire,
+            ProjectileTypes::LightSwords => DamageTypes::Arcane,
+            ProjectileTypes::Burn => DamageTypes::Fire,
+            ProjectileTypes::Poison => DamageTypes::Physical,
+            ProjectileTypes::Bomb => DamageTypes::Physical,
+        }
+    }
+
+    // damage over time never crits
+    pub fn can_crit(&self) -> bool {
+        !matches!(
+            self,
+            ProjectileTypes::Burn | ProjectileTypes::Poison | ProjectileTypes::Bomb
+        )
+    }
+
+    // the bomb damage is already what the enemy has to lose
+    pub fn ignores_resistances(&self) -> bool {
+        matches!(self, ProjectileTypes::Bomb)
+    }
+
+    // status effects are not owned by a single weapon
+    pub fn weapon(&self) -> Option<WeaponsTypes> {
+        match self {
+            ProjectileTypes::Claw => Some(WeaponsTypes::Claw),
+            ProjectileTypes::FireArea => Some(WeaponsTypes::FireArea),
+            ProjectileTypes::ArcaneMissile
+            | ProjectileTypes::ArcaneMissileSplit
+            | ProjectileTypes::ArcaneMissileExplosion => Some(WeaponsTypes::ArcaneMissile),
+            ProjectileTypes::Shuriken | ProjectileTypes::ShurikenMini => {
+                Some(WeaponsTypes::Shuriken)
+            }
+            ProjectileTypes::ChainLightning => Some(WeaponsTypes::ChainLightning),
+            ProjectileTypes::SlowDome => Some(WeaponsTypes::SlowDome),
+            ProjectileTypes::BouncingBall | ProjectileTypes::BouncingBallSplit => {
+                Some(WeaponsTypes::BouncingBall)
+            }
+            ProjectileTypes::FireBoots => Some(WeaponsTypes::FireBoots),
+            ProjectileTypes::LightSwords => Some(WeaponsTypes::LightSwords),
+            ProjectileTypes::Burn | ProjectileTypes::Poison | ProjectileTypes::Bomb => None,
+        }
+    }
+}
+
+#[derive(Debug, Eq, PartialEq, Hash, Clone, Copy, Deserialize, Serialize)]
+pub enum DamageTypes {
+    Physical,
+    Fire,
+    Arcane,
+    Lightning,
 }
 
 #[derive(Resource, Debug)]
@@ -420,17 +809,94 @@ pub struct PlayerUpgradeWeapons {
 // ###################################################################
 // ###################################################################
 // ###################################################################
-// AURA
-#[derive(Component)]
-pub struct VelocityAura {
+// STATUS EFFECTS
+#[derive(Debug, PartialEq, Eq, Hash, Clone, Copy, Deserialize, Serialize)]
+pub enum StatusEffectTypes {
+    Burn,
+    Poison,
+    Slow,
+    Stun,
+    Freeze,
+    Vulnerability,
+    KnockbackResistance,
+}
+
+#[derive(Debug, PartialEq, Clone, Copy)]
+pub enum StatusEffectStacking {
+    // applying the effect again only resets the lifetime, the strongest value is kept
+    Refresh,
+    // applying the effect again adds a stack (up to max_stacks) and resets the lifetime
+    StackIntensity { max_stacks: u32 },
+}
+
+impl StatusEffectTypes {
+    pub fn stacking(&self) -> StatusEffectStacking {
+        match self {
+            StatusEffectTypes::Burn => StatusEffectStacking::StackIntensity { max_stacks: 5 },
+            StatusEffectTypes::Poison => StatusEffectStacking::StackIntensity { max_stacks: 10 },
+            StatusEffectTypes::Slow => StatusEffectStacking::Refresh,
+            StatusEffectTypes::Stun => StatusEffectStacking::Refresh,
+            StatusEffectTypes::Freeze => StatusEffectStacking::Refresh,
+            StatusEffectTypes::Vulnerability => {
+                StatusEffectStacking::StackIntensity { max_stacks: 5 }
+            }
+            StatusEffectTypes::KnockbackResistance => StatusEffectStacking::Refresh,
+        }
+    }
+
+    // tint used to show the effect on the enemy, all active tints are blended together
+    pub fn color(&self) -> Color {
+        match self {
+            StatusEffectTypes::Burn => Color::rgb(1.0, 0.45, 0.1),
+            StatusEffectTypes::Poison => Color::rgb(0.4, 1.0, 0.3),
+            StatusEffectTypes::Slow => Color::rgb(0.0, 0.0, 1.0),
+            StatusEffectTypes::Stun => Color::rgb(0.0, 0.0, 0.0),
+            StatusEffectTypes::Freeze => Color::rgb(0.55, 0.9, 1.0),
+            StatusEffectTypes::Vulnerability => Color::rgb(0.8, 0.2, 0.8),
+            StatusEffectTypes::KnockbackResistance => Color::rgb(0.6, 0.6, 0.6),
+        }
+    }
+}
+
+pub struct StatusEffect {
+    pub effect_type: StatusEffectTypes,
+    // meaning depends on the type:
+    // Burn/Poison: damage per second per stack
+    // Slow: percentage of speed removed (0.5 = half speed)
+    // Vulnerability: extra damage taken per stack (0.1 = +10%)
+    // KnockbackResistance: percentage of impulse ignored (1.0 = no knockback)
+    // Stun/Freeze: unused
     pub value: f32,
+    pub stacks: u32,
     pub lifetime: Timer,
+    pub tick: Timer,
 }
 
-#[derive(Component)]
-pub struct StunAura {
-    pub lifetime: Timer,
+#[derive(Component, Default)]
+pub struct StatusEffects {
+    pub effects: Vec<StatusEffect>,
 }
+
+impl StatusEffects {
+    pub fn get(&self, effect_type: StatusEffectTypes) -> Option<&StatusEffect> {
+        self.effects
+            .iter()
+            .find(|effect| effect.effect_type == effect_type)
+    }
+
+    // value multiplied by the amount of stacks, 0.0 when the effect is not active
+    pub fn intensity(&self, effect_type: StatusEffectTypes) -> f32 {
+        self.get(effect_type)
+            .map_or(0.0, |effect| effect.value * effect.stacks as f32)
+    }
+
+    pub fn is_immobilized(&self) -> bool {
+        self.get(StatusEffectTypes::Stun).is_some() || self.get(StatusEffectTypes::Freeze).is_some()
+    }
+}
+
+#[derive(Component, Deref, DerefMut)]
+pub struct StatusEffectImmunities(pub Vec<StatusEffectTypes>);
 // ###################################################################
 // ###################################################################
 // ###################################################################
@@ -447,6 +913,442 @@ pub struct GemBoss;
 #[derive(Component)]
 pub struct GemIsAttracted;
 
+// Single gem collecting the experience of every gem far away from the player
+#[derive(Component)]
+pub struct GemOverflow;
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum GemTiers {
+    Blue,
+    Green,
+    Red,
+}
+
+impl GemTiers {
+    pub fn from_experience(experience: u32) -> Self {
+        if experience >= GEM_RED_EXPERIENCE {
+            GemTiers::Red
+        } else if experience >= GEM_GREEN_EXPERIENCE {
+            GemTiers::Green
+        } else {
+            GemTiers::Blue
+        }
+    }
+
+    pub fn texture_path(&self) -> &'static str {
+        match self {
+            GemTiers::Blue => "gem.png",
+            GemTiers::Green => "gem_green.png",
+            GemTiers::Red => "gem_red.png",
+        }
+    }
+}
+
+// ###################################################################
+// ###################################################################
+// ###################################################################
+// ###################################################################
+// PICKUPS
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Deserialize, Serialize)]
+pub enum PickupTypes {
+    FloorChicken,
+    Vacuum,
+    Bomb,
+    GoldCoin,
+    Chest,
+}
+
+impl PickupTypes {
+    pub fn texture_path(&self) -> &'static str {
+        match self {
+            PickupTypes::FloorChicken => "pickups/chicken.png",
+            PickupTypes::Vacuum => "pickups/vacuum.png",
+            PickupTypes::Bomb => "pickups/bomb.png",
+            PickupTypes::GoldCoin => "pickups/coin.png",
+            PickupTypes::Chest => "pickups/chest.png",
+        }
+    }
+}
+
+// chance is between 0 and 1, before luck
+#[derive(Debug, Clone, Deserialize, Serialize)]
+pub struct PickupDrop {
+    pub pickup_type: PickupTypes,
+    pub chance: f32,
+}
+
+impl PickupDrop {
+    pub fn roll(&self, player_stats: &PlayerInGameStats) -> bool {
+        // greed is applied to the coin value, not here
+        rand::thread_rng().gen_range(0.0..1.0) < self.chance * player_stats.luck
+    }
+}
+
+#[derive(Component)]
+pub struct Pickup {
+    pub pickup_type: PickupTypes,
+}
+
+// ###################################################################
+// ###################################################################
+// ###################################################################
+// ###################################################################
+// PROPS
+
+#[derive(Deserialize, Serialize, Debug, Copy, Clone, PartialEq, Eq, Hash)]
+pub enum PropTypes {
+    Brazier,
+    WoodenCrate,
+    Barrel,
+}
+
+#[derive(Resource, Debug, Deserialize, Serialize)]
+pub struct PropsResource {
+    pub brazier: PropData,
+    pub wooden_crate: PropData,
+    pub barrel: PropData,
+}
+
+impl PropsResource {
+    pub fn get(&self, prop_type: PropTypes) -> &PropData {
+        match prop_type {
+            PropTypes::Brazier => &self.brazier,
+            PropTypes::WoodenCrate => &self.wooden_crate,
+            PropTypes::Barrel => &self.barrel,
+        }
+    }
+}
+
+#[derive(Debug, Deserialize, Serialize)]
+pub struct PropData {
+    // Aseprite json export, the "idle" clip is played until destroyed
+    pub animation_path: String,
+    pub health: f32,
+    pub collider_radius: f32,
+    // chance to pick this prop when spawning a new one
+    pub spawn_weight: u32,
+    #[serde(default)]
+    pub drops: Vec<PickupDrop>,
+}
+
+#[derive(Component)]
+pub struct Prop {
+    pub prop_type: PropTypes,
+}
+
+// ###################################################################
+// ###################################################################
+// ###################################################################
+// ###################################################################
+// MAP
+
+// Describe how to build the world from an LDtk level
+#[derive(Resource, Debug, Deserialize, Serialize)]
+pub struct MapData {
+    pub ldtk_path: String,
+    pub level: String,
+    // the level is repeated forever, chunks are loaded this many levels around the player
+    pub chunk_radius: i32,
+    // decorations are placed the same way every time a chunk is loaded
+    pub seed: u64,
+    // where the player starts in the level, 0,0 being the center of the level
+    pub player_start: Vec2,
+    pub obstacle_layers: Vec<ObstacleLayerData>,
+    // distance around each obstacle where nothing can spawn
+    pub spawn_exclusion_margin: f32,
+    // multiply the color of the level tiles, decorations are not tinted
+    #[serde(default)]
+    pub ground_tint: Option<[f32; 3]>,
+    #[serde(default)]
+    pub decorations: Vec<DecorationData>,
+}
+
+// Sprites scattered randomly on top of the level in every chunk
+#[derive(Debug, Deserialize, Serialize)]
+pub struct DecorationData {
+    pub texture_path: String,
+    // one of them is picked for each decoration
+    pub sprites: Vec<Rect>,
+    pub count_per_chunk: u32,
+}
+
+#[derive(Debug, Deserialize, Serialize)]
+pub struct ObstacleLayerData {
+    pub identifier: String,
+    // most obstacles do not fill their whole tile
+    pub collider_size: Vec2,
+}
+
+#[derive(Component)]
+pub struct Obstacle {
+    // world position, the global transform is only known at the end of the frame
+    pub center: Vec2,
+    pub half_size: Vec2,
+}
+
+// A tile of the level, position is the center of the tile in level space
+pub struct MapTile {
+    pub texture: Handle<Image>,
+    pub layout: Handle<TextureAtlasLayout>,
+    pub index: usize,
+    pub position: Vec3,
+    pub flip_x: bool,
+    pub flip_y: bool,
+    pub color: Color,
+}
+
+pub struct MapObstacle {
+    pub position: Vec2,
+    pub half_size: Vec2,
+}
+
+// The LDtk level ready to be copied in every chunk
+#[derive(Resource, Default)]
+pub struct MapLevel {
+    pub level_size: Vec2,
+    // world position of the center of the chunk 0,0
+    pub level_offset: Vec2,
+    pub tiles: Vec<MapTile>,
+    pub obstacles: Vec<MapObstacle>,
+}
+
+impl MapLevel {
+    pub fn chunk_at(&self, position: Vec2) -> IVec2 {
+        ((position - self.level_offset) / self.level_size)
+            .round()
+            .as_ivec2()
+    }
+
+    pub fn chunk_position(&self, chunk: IVec2) -> Vec2 {
+        chunk.as_vec2() * self.level_size + self.level_offset
+    }
+}
+
+#[derive(Resource, Default)]
+pub struct MapChunks {
+    pub loaded: HashMap<IVec2, Entity>,
+}
+
+#[derive(Component)]
+pub struct MapChunk(pub IVec2);
+
+// Chunk still spawning its children, obstacles then tiles then decorations
+#[derive(Component, Default)]
+pub struct MapChunkBuild {
+    pub next: usize,
+}
+
+// Areas of the level where enemies and props can't spawn, in level space centered on 0,0
+#[derive(Resource, Default)]
+pub struct SpawnExclusionZones {
+    pub level_size: Vec2,
+    // world position of the center of the level at the origin
+    pub level_offset: Vec2,
+    pub zones: Vec<Rect>,
+}
+
+impl SpawnExclusionZones {
+    pub fn contains(&self, position: Vec2) -> bool {
+        if self.level_size == Vec2::ZERO {
+            return false;
+        }
+        // the level repeats, bring the position back in the level at the origin
+        let local_position = position - self.level_offset;
+        let local_position =
+            local_position - (local_position / self.level_size).round() * self.level_size;
+        self.zones.iter().any(|zone| zone.contains(local_position))
+    }
+}
+
+// ###################################################################
+// ###################################################################
+// ###################################################################
+// ###################################################################
+// Camera
+
+// Follows the player, the shake is added on top of position
+#[derive(Component)]
+pub struct CameraController {
+    // how fast the camera catches up, 0.0 snaps to the player
+    pub smoothing: f32,
+    // distance ahead of the player while moving
+    pub look_ahead: f32,
+    pub max_shake_offset: f32,
+    // trauma lost per second
+    pub trauma_decay: f32,
+    // 0.0 to 1.0, the shake grows with trauma squared
+    pub trauma: f32,
+    pub position: Vec2,
+}
+
+impl Default for CameraController {
+    fn default() -> Self {
+        Self {
+            smoothing: 8.0,
+            look_ahead: 24.0,
+            max_shake_offset: 8.0,
+            trauma_decay: 1.5,
+            trauma: 0.0,
+            position: Vec2::ZERO,
+        }
+    }
+}
+
+// ###################################################################
+// ###################################################################
+// ###################################################################
+// ###################################################################
+// Audio
+
+#[derive(Debug, Eq, PartialEq, Hash, Clone, Copy, Deserialize, Serialize)]
+pub enum SoundTypes {
+    EnemyHit,
+    EnemyDied,
+    CollectExperience,
+    PlayerHurt,
+    PlayerDied,
+    LevelUp,
+}
+
+#[derive(Debug, Deserialize, Serialize)]
+pub struct SoundData {
+    pub path: String,
+    pub volume: f32,
+    // seconds before the same sound can play again
+    pub min_interval: f32,
+    // copies of the sound playing at the same time
+    pub max_playing: usize,
+}
+
+#[derive(Resource, Debug, Deserialize, Serialize)]
+pub struct AudioData {
+    pub menu_music: String,
+    // seconds for a track to fade in while the other one fades out
+    pub crossfade_duration: f32,
+    pub sounds: HashMap<SoundTypes, SoundData>,
+}
+
+#[derive(Resource, Default)]
+pub struct SoundHandles(pub HashMap<SoundTypes, Handle<AudioSource>>);
+
+// Volume buses, every sound is multiplied by master and its own bus
+#[derive(Resource, Debug, Clone, Copy)]
+pub struct AudioVolumes {
+    pub master: f32,
+    pub music: f32,
+    pub sfx: f32,
+}
+
+impl Default for AudioVolumes {
+    fn default() -> Self {
+        Self {
+            master: 1.0,
+            music: 0.6,
+            sfx: 0.8,
+        }
+    }
+}
+
+#[derive(Component, Deref)]
+pub struct SoundEffect(pub SoundTypes);
+
+#[derive(Component)]
+pub struct MusicTrack {
+    pub path: String,
+    // 0.0 to 1.0, multiplied by the music bus
+    pub fade: f32,
+    pub is_fading_out: bool,
+}
+
+// ###################################################################
+// ###################################################################
+// ###################################################################
+// ###################################################################
+// Settings
+
+// Saved in save/settings.ron, applied at startup and every time it changes
+#[derive(Resource, Debug, Clone, Deserialize, Serialize)]
+#[serde(default)]
+pub struct GameSettings {
+    // window size is the game resolution times this
+    pub window_scale: u32,
+    pub is_fullscreen: bool,
+    pub is_vsync: bool,
+    // integer zoom of the camera, sharp pixels at any window size
+    pub is_pixel_perfect: bool,
+    pub ui_scale: f32,
+    pub master_volume: f32,
+    pub music_volume: f32,
+    pub sfx_volume: f32,
+    pub show_damage_numbers: bool,
+    // 0.0 turns it off
+    pub screen_shake: f32,
+    pub show_fps: bool,
+    pub show_minimap: bool,
+}
+
+impl Default for GameSettings {
+    fn default() -> Self {
+        let volumes = AudioVolumes::default();
+        Self {
+            window_scale: 3,
+            is_fullscreen: false,
+            is_vsync: true,
+            is_pixel_perfect: true,
+            ui_scale: 1.0,
+            master_volume: volumes.master,
+            music_volume: volumes.music,
+            sfx_volume: volumes.sfx,
+            show_damage_numbers: true,
+            screen_shake: 1.0,
+            show_fps: true,
+            show_minimap: true,
+        }
+    }
+}
+
+// Developer only, from environment variables or a .env file
+#[derive(Resource, Default)]
+pub struct DebugFlags {
+    // mouse wheel zoom, DEBUG_ZOOM=1
+    pub is_debug_zoom: bool,
+}
+
+// One line of the settings screen, clicking it goes to the next value
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SettingsOption {
+    WindowScale,
+    Fullscreen,
+    Vsync,
+    PixelPerfect,
+    UiScale,
+    MasterVolume,
+    MusicVolume,
+    SfxVolume,
+    DamageNumbers,
+    ScreenShake,
+    FpsCounter,
+    Minimap,
+}
+
+impl SettingsOption {
+    pub const ALL: [SettingsOption; 12] = [
+        SettingsOption::WindowScale,
+        SettingsOption::Fullscreen,
+        SettingsOption::Vsync,
+        SettingsOption::PixelPerfect,
+        SettingsOption::UiScale,
+        SettingsOption::MasterVolume,
+        SettingsOption::MusicVolume,
+        SettingsOption::SfxVolume,
+        SettingsOption::DamageNumbers,
+        SettingsOption::ScreenShake,
+        SettingsOption::FpsCounter,
+        SettingsOption::Minimap,
+    ];
+}
+
 // ###################################################################
 // ###################################################################
 // ###################################################################
@@ -480,6 +1382,30 @@ pub struct OnEnemyBossDied {
     pub position: Vec3,
 }
 
+#[derive(Event)]
+pub struct OnSpawnPickup {
+    pub pickup_type: PickupTypes,
+    pub position: Vec3,
+}
+
+#[derive(Event)]
+pub struct OnPickupCollected {
+    pub pickup_type: PickupTypes,
+    pub position: Vec3,
+}
+
+#[derive(Event)]
+pub struct OnPropHit {
+    pub prop_entity: Entity,
+    pub damage: f32,
+}
+
+#[derive(Event)]
+pub struct OnPropDestroyed {
+    pub prop_type: PropTypes,
+    pub position: Vec3,
+}
+
 #[derive(Event)]
 pub struct OnEnemyHit {
     pub damage: f32,
@@ -490,9 +1416,31 @@ pub struct OnEnemyHit {
     pub projectile_type: ProjectileTypes,
 }
 
+// Sent once the damage of an OnEnemyHit has been computed (crit, resistances, ...)
+// and removed from the enemy health
+#[derive(Event)]
+pub struct OnEnemyReceivedDamage {
+    pub damage: f32,
+    pub enemy_entity: Entity,
+    pub position: Vec3,
+    pub damage_type: DamageTypes,
+    pub is_critical: bool,
+    pub is_killing_blow: bool,
+    pub projectile_type: ProjectileTypes,
+}
+
+#[derive(Event)]
+pub struct OnApplyStatusEffect {
+    pub enemy_entity: Entity,
+    pub effect_type: StatusEffectTypes,
+    pub value: f32,
+    pub duration: f32,
+}
+
 #[derive(Event)]
 pub struct OnPlayerReceivedDamage {
     pub damage: f32,
+    pub enemy_type: EnemyTypes,
 }
 
 #[derive(Event)]
@@ -503,6 +1451,34 @@ pub struct OnCollectExperience {
 #[derive(Event)]
 pub struct OnSpawnEnemy {
     pub enemy_types: EnemyTypes,
+    // None picks a random position outside the screen
+    pub position: Option<Vec2>,
+    // enemies with a direction walk straight instead of chasing the player
+    pub direction: Option<Vec2>,
+}
+
+// Frame event from the Aseprite tag user data, ex: "1:hitbox"
+#[derive(Event)]
+pub struct OnAnimationEvent {
+    pub entity: Entity,
+    pub clip: String,
+    pub name: String,
+}
+
+#[derive(Event)]
+pub struct OnPlaySound {
+    pub sound: SoundTypes,
+}
+
+#[derive(Event)]
+pub struct OnCameraShake {
+    pub trauma: f32,
+}
+
+#[derive(Event)]
+pub struct OnAnimationFinished {
+    pub entity: Entity,
+    pub clip: String,
 }
 
 // ###################################################################
@@ -536,6 +1512,38 @@ pub struct PlayerLevelUI;
 #[derive(Component)]
 pub struct MainMenuPlayButton;
 
+#[derive(Component)]
+pub struct MainMenuSettingsButton;
+
+#[derive(Component)]
+pub struct SettingsUI;
+
+#[derive(Component)]
+pub struct SettingsButton {
+    pub option: SettingsOption,
+}
+
+#[derive(Component)]
+pub struct SettingsBackButton;
+
+#[derive(Component)]
+pub struct MainMenuDifficultyButton {
+    pub difficulty: Difficulty,
+}
+
+#[derive(Component)]
+pub struct MainMenuAimModeButton {
+    pub aim_mode: AimMode,
+}
+
+#[derive(Component)]
+pub struct StageSelectUI;
+
+#[derive(Component)]
+pub struct StageSelectButton {
+    pub stage_index: usize,
+}
+
 #[derive(Component)]
 pub struct ButtonItemUpgrade {
     pub item_key: String,
@@ -551,6 +1559,15 @@ pub struct ButtonWeaponUpgrade {
 pub struct ButtonWeaponChoose {
     pub item: WeaponsTypes,
 }
+#[derive(Component)]
+pub struct PauseUI;
+
+#[derive(Component)]
+pub struct GameOverUI;
+
+#[derive(Component)]
+pub struct GameOverQuitButton;
+
 #[derive(Component)]
 pub struct WorldTextUI {
     pub lifetime: Timer,
@@ -558,6 +1575,56 @@ pub struct WorldTextUI {
     pub position: Vec2,
 }
 
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum IndicatorTypes {
+    Boss,
+    BossGem,
+    Chest,
+}
+
+impl IndicatorTypes {
+    pub fn icon_path(&self) -> &'static str {
+        match self {
+            IndicatorTypes::Boss => "indicator_boss.png",
+            IndicatorTypes::BossGem => "gem_boss.png",
+            IndicatorTypes::Chest => PickupTypes::Chest.texture_path(),
+        }
+    }
+
+    pub fn color(&self) -> Color {
+        match self {
+            IndicatorTypes::Boss => Color::rgb(0.9, 0.2, 0.2),
+            IndicatorTypes::BossGem => Color::rgb(0.3, 0.8, 1.0),
+            IndicatorTypes::Chest => Color::rgb(1.0, 0.8, 0.2),
+        }
+    }
+}
+
+// World entities with this get an arrow on the edge of the screen when off camera
+#[derive(Component)]
+pub struct OffScreenIndicator(pub IndicatorTypes);
+
+#[derive(Component)]
+pub struct OffScreenIndicatorUI {
+    pub target: Entity,
+}
+
+#[derive(Component)]
+pub struct OffScreenIndicatorArrowUI;
+
+#[derive(Component)]
+pub struct OffScreenIndicatorDistanceUI;
+
+#[derive(Component)]
+pub struct MinimapUI;
+
+// Texture drawn from the world around the player, redrawn every MINIMAP_UPDATE_INTERVAL
+#[derive(Resource)]
+pub struct Minimap {
+    pub image: Handle<Image>,
+    pub timer: Timer,
+}
+
 // ###################################################################
 // ###################################################################
 // ###################################################################
@@ -577,7 +1644,7 @@ pub struct ProjectileBundleCollider {
 impl Default for ProjectileBundleCollider {
     fn default() -> Self {
         Self {
-            collision_group: CollisionGroups::new(PROJECTILE_GROUP, ENEMY_GROUP),
+            collision_group: CollisionGroups::new(PROJECTILE_GROUP, ENEMY_GROUP | PROP_GROUP),
             active_events: ActiveEvents::COLLISION_EVENTS,
             colliding_entities: CollidingEntities::default(),
         }
@@ -724,6 +1791,31 @@ pub struct Wave {
     pub enemy_type: EnemyTypes,
     pub delay_between_spawn: Timer,
     pub amount_per_timer_trigger: u32,
+    pub spawn_pattern: SpawnPattern,
+}
+
+// How the enemies of one timer trigger are placed, amount_per_timer_trigger is the size of the group
+#[derive(Debug, Default, Clone, Copy, PartialEq, Deserialize, Serialize)]
+pub enum SpawnPattern {
+    // each enemy on a random point outside the screen
+    #[default]
+    Random,
+    // circle around the player, closing in
+    Ring {
+        radius: f32,
+    },
+    // line on one side of the screen walking straight across
+    Wall {
+        spacing: f32,
+    },
+    // tight group flying straight through the screen, gone once far away
+    Swarm {
+        spread: f32,
+    },
+    // tight group on one side of the screen chasing the player
+    Pack {
+        spread: f32,
+    },
 }
 
 #[derive(Resource)]
@@ -731,6 +1823,184 @@ pub struct WaveManagerGlobalTime {
     pub global_time: Stopwatch,
 }
 
+// Wave script of a stage, loaded from a ron file
+#[derive(Debug, Deserialize, Serialize)]
+pub struct WavesData {
+    pub wave_managers: Vec<WaveManagerData>,
+}
+
+#[derive(Debug, Deserialize, Serialize)]
+pub struct WaveManagerData {
+    pub name: String,
+    // seconds since the start of the run
+    pub start: f32,
+    pub end: f32,
+    pub waves: Vec<WaveData>,
+}
+
+#[derive(Debug, Deserialize, Serialize)]
+pub struct WaveData {
+    pub enemy_type: EnemyTypes,
+    pub delay_between_spawn: f32,
+    // a wave spawning only once is used for bosses
+    #[serde(default)]
+    pub once: bool,
+    pub amount_per_timer_trigger: u32,
+    #[serde(default)]
+    pub spawn_pattern: SpawnPattern,
+}
+
+impl WaveData {
+    pub fn to_wave(&self) -> Wave {
+        let timer_mode = match self.once {
+            true => TimerMode::Once,
+            false => TimerMode::Repeating,
+        };
+        Wave {
+            enemy_type: self.enemy_type,
+            delay_between_spawn: Timer::from_seconds(self.delay_between_spawn, timer_mode),
+            amount_per_timer_trigger: self.amount_per_timer_trigger,
+            spawn_pattern: self.spawn_pattern,
+        }
+    }
+}
+
+// ###################################################################
+// ###################################################################
+// ###################################################################
+// ###################################################################
+// Stages
+
+#[derive(Resource, Debug, Deserialize, Serialize)]
+pub struct StagesResource {
+    pub stages: Vec<StageData>,
+}
+
+// Everything that makes a stage different from another one
+#[derive(Debug, Deserialize, Serialize)]
+pub struct StageData {
+    pub name: String,
+    pub description: String,
+    pub map_path: String,
+    pub waves_path: String,
+    // enemies that can show up during the stage, waves can only use those
+    pub enemy_roster: Vec<EnemyTypes>,
+    pub music_path: Option<String>,
+    // seconds to survive before the reaper comes
+    pub duration: f32,
+    pub reaper: Option<EnemyTypes>,
+    // the stage is locked until this stage has been survived
+    #[serde(default)]
+    pub unlocked_by: Option<String>,
+    #[serde(default)]
+    pub difficulty_curve: DifficultyCurveData,
+    // the camera and the player stay inside, the map repeats forever without it
+    #[serde(default)]
+    pub camera_bounds: Option<StageBoundsData>,
+}
+
+// World rect of the stage
+#[derive(Debug, Clone, Copy, Deserialize, Serialize)]
+pub struct StageBoundsData {
+    pub min_x: f32,
+    pub min_y: f32,
+    pub max_x: f32,
+    pub max_y: f32,
+}
+
+// Index in StagesResource.stages, set by the stage select screen
+#[derive(Resource, Default)]
+pub struct SelectedStage(pub usize);
+
+// How fast the stage gets harder, 0.1 means +10%
+#[derive(Debug, Clone, Deserialize, Serialize)]
+pub struct DifficultyCurveData {
+    pub health_per_minute: f32,
+    pub health_per_level: f32,
+    pub spawn_per_minute: f32,
+    // enemies get tankier when the player dps is above this one, None ignores the dps
+    #[serde(default)]
+    pub dps_reference: Option<f32>,
+}
+
+impl Default for DifficultyCurveData {
+    fn default() -> Self {
+        Self {
+            health_per_minute: 0.1,
+            health_per_level: 0.02,
+            spawn_per_minute: 0.05,
+            dps_reference: None,
+        }
+    }
+}
+
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub enum Difficulty {
+    Easy,
+    #[default]
+    Normal,
+    Hard,
+    Hyper,
+}
+
+impl Difficulty {
+    pub const ALL: [Difficulty; 4] = [
+        Difficulty::Easy,
+        Difficulty::Normal,
+        Difficulty::Hard,
+        Difficulty::Hyper,
+    ];
+
+    // multipliers applied on top of the stage curve
+    pub fn modifiers(&self) -> DifficultyScaling {
+        match self {
+            Difficulty::Easy => DifficultyScaling {
+                health: 0.75,
+                spawn_amount: 0.75,
+                speed: 1.0,
+                damage: 0.75,
+            },
+            Difficulty::Normal => DifficultyScaling::default(),
+            Difficulty::Hard => DifficultyScaling {
+                health: 1.3,
+                spawn_amount: 1.25,
+                speed: 1.0,
+                damage: 1.25,
+            },
+            Difficulty::Hyper => DifficultyScaling {
+                health: 1.6,
+                spawn_amount: 1.5,
+                speed: 1.25,
+                damage: 1.5,
+            },
+        }
+    }
+}
+
+// Set from the main menu
+#[derive(Resource, Default)]
+pub struct SelectedDifficulty(pub Difficulty);
+
+// Current multipliers for new enemies, updated during the run
+#[derive(Resource, Debug, Clone, Copy)]
+pub struct DifficultyScaling {
+    pub health: f32,
+    pub spawn_amount: f32,
+    pub speed: f32,
+    pub damage: f32,
+}
+
+impl Default for DifficultyScaling {
+    fn default() -> Self {
+        Self {
+            health: 1.0,
+            spawn_amount: 1.0,
+            speed: 1.0,
+            damage: 1.0,
+        }
+    }
+}
+
 // ###################################################################
 // ###################################################################
 // ###################################################################
@@ -802,3 +2072,80 @@ pub struct ItemEffect {
 pub struct ShadowTrackedEntity {
     pub target: Entity,
 }
+
+// ###################################################################
+// ###################################################################
+// ###################################################################
+// ###################################################################
+// Statistics
+
+#[derive(Debug, Default, Clone, Serialize)]
+pub struct DamageStatistics {
+    pub damage: f32,
+    pub hits: u32,
+    pub kills: u32,
+    pub time_equipped: f32,
+}
+
+impl DamageStatistics {
+    pub fn dps(&self) -> f32 {
+        if self.time_equipped <= 0.0 {
+            return 0.0;
+        }
+        self.damage / self.time_equipped
+    }
+}
+
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, Serialize)]
+pub enum RunOutcome {
+    #[default]
+    InProgress,
+    Died,
+    // the stage duration was reached, the reaper is coming
+    Survived,
+}
+
+#[derive(Resource, Debug, Default, Clone, Serialize)]
+pub struct RunStatistics {
+    pub outcome: RunOutcome,
+    pub run_time: f32,
+    pub enemies_killed: u32,
+    pub gold_collected: u32,
+    pub props_destroyed: u32,
+    pub weapons: HashMap<WeaponsTypes, DamageStatistics>,
+    pub projectiles: HashMap<ProjectileTypes, DamageStatistics>,
+    pub damage_taken: HashMap<EnemyTypes, f32>,
+}
+
+// ###################################################################
+// ###################################################################
+// ###################################################################
+// ###################################################################
+// Pools
+
+// Entities waiting to be reused, kept hidden and without collider while in the pool
+// projectiles are kept by type so a reused one already has the right sprite and collider
+#[derive(Resource, Default)]
+pub struct ProjectilePool {
+    pub free: HashMap<ProjectileTypes, Vec<Entity>>,
+}
+
+#[derive(Resource, Default)]
+pub struct GemPool {
+    pub free: Vec<Entity>,
+}
+
+#[derive(Resource, Default)]
+pub struct WorldTextPool {
+    pub free: Vec<Entity>,
+}
+
+// tile size, columns, rows, padding and offset of a grid layout
+pub type TextureAtlasGridKey = ([u32; 2], usize, usize, [u32; 2], [u32; 2]);
+
+#[derive(Resource, Default)]
+pub struct TextureAtlasLayoutCache {
+    pub layouts: HashMap<TextureAtlasGridKey, Handle<TextureAtlasLayout>>,
+    // by json path
+    pub sheets: HashMap<String, Arc<AnimationSheet>>,
+}
@@ -2,6 +2,18 @@ use bevy::prelude::Color;
 use bevy_rapier2d::prelude::*;
 pub const SCREEN_WIDTH: i32 = 640;
 pub const SCREEN_HEIGHT: i32 = 360;
+// zoom of the camera when pixel perfect is off
+pub const CAMERA_SCALE: f32 = 0.5;
+// the ui is laid out for a window this many times the game resolution
+pub const UI_REFERENCE_ZOOM: f32 = 3.0;
+pub const DEBUG_ZOOM_MIN_SCALE: f32 = 0.25;
+pub const DEBUG_ZOOM_MAX_SCALE: f32 = 4.0;
+// screen shake
+pub const PLAYER_HIT_TRAUMA: f32 = 0.3;
+pub const PLAYER_HIT_SHAKE_COOLDOWN: f32 = 0.5;
+pub const EXPLOSION_TRAUMA: f32 = 0.15;
+pub const EXPLOSION_SHAKE_COOLDOWN: f32 = 0.4;
+pub const BOSS_SPAWN_TRAUMA: f32 = 0.7;
 
 // start at level 2 because player is already level 1
 pub const MAP_LEVEL_EXPERIENCE: [u32; 25] = [
@@ -15,8 +27,15 @@ pub const MAX_LEVEL: u32 = 5;
 pub const PLAYER_GROUP: Group = Group::GROUP_1;
 pub const ENEMY_GROUP: Group = Group::GROUP_2;
 pub const PROJECTILE_GROUP: Group = Group::GROUP_3;
+pub const OBSTACLE_GROUP: Group = Group::GROUP_27;
+pub const PROP_GROUP: Group = Group::GROUP_28;
+pub const PICKUP_GROUP: Group = Group::GROUP_29;
 pub const GEM_GROUP: Group = Group::GROUP_30;
 
+pub const PLAYER_COLLIDER_RADIUS: f32 = 4.0;
+// right stick is ignored below this
+pub const AIM_STICK_DEAD_ZONE: f32 = 0.3;
+
 // pub const FONT: &str = "fonts/dogica.ttf";
 // pub const DAMAGE_FONT_SIZE: f32 = 11.0;
 
@@ -25,12 +44,26 @@ pub const GEM_GROUP: Group = Group::GROUP_30;
 
 pub const DAMAGE_FONT: &str = "fonts/VCR_OSD_MONO_1.001.ttf";
 pub const DAMAGE_FONT_SIZE: f32 = 18.0;
+pub const DAMAGE_CRITICAL_FONT_SIZE: f32 = 26.0;
 // pub const FONT: &str = "fonts/VT323-Regular.ttf";
 // pub const DAMAGE_FONT_SIZE: f32 = 22.0;
 
 // pub const DAMAGE_FONT_COLOR: Color = Color::rgb(0.164, 0.686, 0.905); // blue
 // pub const DAMAGE_FONT_COLOR: Color = Color::rgb(0.937, 0.956, 0.207); // yellow
 pub const DAMAGE_FONT_COLOR: Color = Color::WHITE; // white
+// off-screen indicators, in ui pixels
+pub const OFF_SCREEN_INDICATOR_SIZE: f32 = 32.0;
+pub const OFF_SCREEN_INDICATOR_ARROW_SIZE: f32 = 9.0;
+pub const OFF_SCREEN_INDICATOR_MARGIN: f32 = 24.0;
+// minimap, the texture covers MINIMAP_WORLD_RADIUS around the player
+pub const MINIMAP_TEXTURE_SIZE: u32 = 64;
+pub const MINIMAP_UI_SIZE: f32 = 128.0;
+pub const MINIMAP_WORLD_RADIUS: f32 = 640.0;
+pub const MINIMAP_UPDATE_INTERVAL: f32 = 0.1;
+// enemies on the same minimap pixel for the hottest color
+pub const MINIMAP_ENEMY_HEAT_MAX: f32 = 4.0;
+// distances are shown in meters
+pub const PIXELS_PER_METER: f32 = 16.0;
 
 pub const FONT: &str = "fonts/FiraMono-Medium.ttf";
 pub const FONT_BOLD: &str = "fonts/FiraSans-Bold.ttf";
@@ -42,5 +75,35 @@ pub const PROJECTILE_Z_INDEX: f32 = 199.0;
 pub const GEM_Z_INDEX: f32 = 50.0; // under enemies makes it better
 
 pub const GEM_BOSS_Z_INDEX: f32 = 999.0; // Above anything
+pub const PICKUP_Z_INDEX: f32 = 51.0; // just above gems
+pub const PROP_Z_INDEX: f32 = 52.0;
 
 pub const SHADOW_Z_INDEX: f32 = 1.0;
+pub const DEATH_EFFECT_Z_INDEX: f32 = 100.0; // just above enemies
+pub const MAP_Z_INDEX: f32 = -1.0; // each map layer is drawn slightly above the previous one
+pub const MAP_DECORATION_Z_INDEX: f32 = -0.5;
+// entities spawned per frame while chunks are building, a whole chunk is over a thousand
+pub const MAP_CHUNK_SPAWN_BUDGET: usize = 600;
+
+// gems
+pub const GEM_GREEN_EXPERIENCE: u32 = 10;
+pub const GEM_RED_EXPERIENCE: u32 = 50;
+// past this amount of gems on the ground they start merging together
+pub const MAX_GEMS_BEFORE_MERGE: usize = 300;
+pub const GEM_MERGE_RADIUS: f32 = 48.0;
+
+// enemies
+pub const MAX_ENEMIES: u32 = 600;
+// farther than this enemies are moved back just outside the screen
+pub const ENEMY_RECYCLE_DISTANCE: f32 = 750.0;
+pub const ENEMY_DEATH_DURATION: f32 = 0.4;
+pub const ENEMY_HIT_FLASH_DURATION: f32 = 0.08;
+
+// pickups
+pub const FLOOR_CHICKEN_HEAL: f32 = 30.0;
+pub const GOLD_COIN_VALUE: f32 = 1.0;
+
+// props
+pub const PROP_SPAWN_DELAY: f32 = 4.0;
+pub const MAX_PROPS: usize = 12;
+pub const PROP_DESPAWN_DISTANCE: f32 = 1000.0;
@@ -1,2 +1,4 @@
 pub mod enemy;
 pub mod enemy_bundle;
+pub mod enemy_death;
+pub mod enemy_population;
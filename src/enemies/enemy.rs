@@ -1,10 +1,14 @@
 use crate::components::*;
-use crate::constants::{ENEMY_Z_INDEX, SHADOW_Z_INDEX};
+use crate::constants::{
+    ENEMY_DEATH_DURATION, ENEMY_RECYCLE_DISTANCE, ENEMY_Z_INDEX, SHADOW_Z_INDEX,
+};
 use crate::enemies::enemy_bundle::EnemyBundle;
 use crate::math_utils::get_random_position_outside_screen;
+use crate::pools::pool::TextureAtlasLayouts;
 use bevy::input::common_conditions::input_pressed;
 use bevy::prelude::*;
 use bevy_rapier2d::prelude::*;
+use rand::Rng;
 use std::fs;
 
 pub struct EnemyPlugin;
@@ -22,8 +26,8 @@ impl Plugin for EnemyPlugin {
                 enemy_death_check,
                 enemy_applied_impulse,
                 compute_enemy_velocity,
-                apply_slow_aura_on_enemy,
-                apply_stun_aura_on_enemy,
+                compute_enemy_fixed_velocity,
+                apply_status_effects_on_enemy_velocity,
                 apply_enemy_velocity.run_if(not(input_pressed(KeyCode::Space))),
             )
                 .chain()
@@ -44,45 +48,31 @@ impl Plugin for EnemyPlugin {
     }
 }
 
-fn spawn_enemy(
+pub fn spawn_enemy(
     mut commands: Commands,
     asset_server: Res<AssetServer>,
-    mut texture_atlas_layouts: ResMut<Assets<TextureAtlasLayout>>,
+    mut texture_atlas_layouts: TextureAtlasLayouts,
     mut spawn_enemy: EventReader<OnSpawnEnemy>,
     player: Query<&Transform, With<Player>>,
     enemies_resource: Res<EnemiesResource>,
-    enemies: Query<Entity, With<Enemy>>,
+    mut population: ResMut<EnemyPopulation>,
     player_stats: Res<PlayerInGameStats>,
+    spawn_exclusion_zones: Res<SpawnExclusionZones>,
+    difficulty_scaling: Res<DifficultyScaling>,
 ) {
     let player = player.single();
     for event in spawn_enemy.read() {
-        let enemy_data = match event.enemy_types {
-            EnemyTypes::Bat => &enemies_resource.bat,
-            EnemyTypes::Bee => &enemies_resource.bee,
-            EnemyTypes::Golem => &enemies_resource.golem,
-            EnemyTypes::Rabbit => &enemies_resource.rabbit,
-            EnemyTypes::Skull => &enemies_resource.skull,
-            EnemyTypes::BossWolf => &enemies_resource.boss_wolf,
-        };
-
-        // Do not spawn more enemies if we already have more than 400
-        // except bosses
-        if !enemy_data.is_boss && !enemy_data.is_semi_boss {
-            if enemies.iter().count() > 600 {
-                println!("Too many enemies, no more !");
-                return;
-            }
+        let enemy_data = enemies_resource.get(event.enemy_types);
+
+        // bosses are always spawned, others only if their type has room left
+        if enemy_data.is_boss || enemy_data.is_semi_boss {
+            population.add(event.enemy_types);
+        } else if !population.try_add(event.enemy_types, enemy_data.population_budget) {
+            continue;
         }
 
-        let texture = asset_server.load(&enemy_data.texture_path);
-        let layout = TextureAtlasLayout::from_grid(
-            enemy_data.texture_layout_size,
-            enemy_data.texture_layout_columns,
-            enemy_data.texture_layout_rows,
-            Option::from(Vec2::new(0.0, 0.0)),
-            None,
-        );
-        let texture_atlas_layout = texture_atlas_layouts.add(layout);
+        let sheet = texture_atlas_layouts.aseprite(&enemy_data.animation_path);
+        let texture = asset_server.load(&sheet.texture_path);
 
         let new_enemy = commands
             .spawn((
@@ -90,32 +80,39 @@ fn spawn_enemy(
                     sprite_bundle: SpriteBundle {
                         texture: texture.clone(),
                         transform: Transform {
-                            translation: get_random_position_outside_screen(
-                                player.translation.xy(),
-                            )
-                            .extend(ENEMY_Z_INDEX), // always in front
+                            translation: event
+                                .position
+                                .unwrap_or_else(|| {
+                                    get_random_position_outside_screen(
+                                        player.translation.xy(),
+                                        &spawn_exclusion_zones,
+                                    )
+                                })
+                                .extend(ENEMY_Z_INDEX), // always in front
                             rotation: Default::default(),
                             scale: Vec3::new(1.0, 1.0, 0.0),
                         },
                         ..default()
                     },
                     texture_atlas: TextureAtlas {
-                        layout: texture_atlas_layout.clone(),
+                        layout: sheet.layout.clone(),
                         index: 0,
                     },
-                    animation_indices: AnimationIndices {
-                        first: 0,
-                        last: enemy_data.animation_last_indice,
-                        is_repeating: true,
-                    },
-                    health: Health(enemy_data.health * player_stats.curse),
-                    enemy_speed: EnemySpeed(enemy_data.speed * player_stats.curse),
+                    health: Health(
+                        enemy_data.health * player_stats.curse * difficulty_scaling.health,
+                    ),
+                    enemy_speed: EnemySpeed(
+                        enemy_data.speed * player_stats.curse * difficulty_scaling.speed,
+                    ),
                     enemy_damage_overtime: EnemyDamageOverTime(
-                        enemy_data.damage * player_stats.curse,
+                        enemy_data.damage * player_stats.curse * difficulty_scaling.damage,
                     ),
                     collider: Collider::capsule_x(3.0, 12.0 / 2.0),
                     ..default()
                 },
+                EnemyType(event.enemy_types),
+                SpriteAnimation::new(sheet, "run"),
+                AnimationController::moving(),
                 // Velocity {
                 //     linvel: Vec2::new(0.0, 0.0),
                 //     angvel: 0.0,
@@ -130,7 +127,37 @@ fn spawn_enemy(
         }
 
         if enemy_data.is_boss {
-            commands.entity(new_enemy).insert(EnemyBossDrop);
+            commands
+                .entity(new_enemy)
+                .insert((EnemyBossDrop, OffScreenIndicator(IndicatorTypes::Boss)));
+        }
+
+        if let Some(direction) = event.direction {
+            commands
+                .entity(new_enemy)
+                .insert(EnemyFixedDirection(direction));
+        }
+
+        if enemy_data.is_unkillable {
+            commands.entity(new_enemy).insert(EnemyUnkillable);
+        }
+
+        if !enemy_data.immunities.is_empty() {
+            commands
+                .entity(new_enemy)
+                .insert(StatusEffectImmunities(enemy_data.immunities.clone()));
+        }
+
+        if !enemy_data.resistances.is_empty() {
+            commands
+                .entity(new_enemy)
+                .insert(EnemyResistances(enemy_data.resistances.clone()));
+        }
+
+        if !enemy_data.drops.is_empty() {
+            commands
+                .entity(new_enemy)
+                .insert(EnemyDrops(enemy_data.drops.clone()));
         }
 
         // handle shadow
@@ -150,7 +177,10 @@ fn spawn_enemy(
 
 fn compute_enemy_velocity(
     player: Query<&Transform, (With<Player>, Without<Enemy>)>,
-    mut enemies: Query<(&Transform, &mut Sprite, &mut EnemyVelocity, &EnemySpeed), (With<Enemy>,)>,
+    mut enemies: Query<
+        (&Transform, &mut Sprite, &mut EnemyVelocity, &EnemySpeed),
+        Without<EnemyFixedDirection>,
+    >,
     time: Res<Time>,
 ) {
     let player_transform = player.single();
@@ -165,56 +195,38 @@ fn compute_enemy_velocity(
     }
 }
 
-fn apply_slow_aura_on_enemy(
-    mut commands: Commands,
-    mut enemies: Query<(Entity, &mut EnemyVelocity, &mut VelocityAura, &mut Sprite)>,
+// no chasing, they keep going until too far from the player
+fn compute_enemy_fixed_velocity(
+    mut enemies: Query<(
+        &mut Sprite,
+        &mut EnemyVelocity,
+        &EnemySpeed,
+        &EnemyFixedDirection,
+    )>,
     time: Res<Time>,
 ) {
-    for (entity, mut velocity, mut aura, mut sprite) in &mut enemies {
-        velocity.x *= aura.value;
-        velocity.y *= aura.value;
-
-        aura.lifetime.tick(time.delta());
-
-        sprite.color = Color::Rgba {
-            red: 0.0,
-            green: 0.0,
-            blue: 1.0,
-            alpha: 1.0,
-        };
-
-        if aura.lifetime.just_finished() {
-            sprite.color = Color::WHITE;
-            commands.entity(entity).remove::<VelocityAura>();
-        }
+    for (mut sprite, mut velocity, speed, direction) in &mut enemies {
+        sprite.flip_x = direction.x < 0.0;
+        // velocity is removed from the position
+        velocity.0 = -direction.0 * time.delta_seconds() * speed.0;
     }
 }
 
-fn apply_stun_aura_on_enemy(
-    mut commands: Commands,
-    mut enemies: Query<(Entity, &mut EnemyVelocity, &mut StunAura, &mut Sprite)>,
-    time: Res<Time>,
+fn apply_status_effects_on_enemy_velocity(
+    mut enemies: Query<(&mut EnemyVelocity, &StatusEffects), With<Enemy>>,
 ) {
-    for (entity, mut velocity, mut aura, mut sprite) in &mut enemies {
-        velocity.x = 0.0;
-        velocity.y = 0.0;
-
-        commands.entity(entity).try_insert(RigidBody::Fixed);
-
-        aura.lifetime.tick(time.delta());
-
-        sprite.color = Color::Rgba {
-            red: 0.0,
-            green: 0.0,
-            blue: 0.0,
-            alpha: 1.0,
-        };
-
-        if aura.lifetime.just_finished() {
-            sprite.color = Color::WHITE;
-            commands.entity(entity).remove::<StunAura>();
-            commands.entity(entity).try_insert(RigidBody::Dynamic);
+    for (mut velocity, status_effects) in &mut enemies {
+        if status_effects.is_immobilized() {
+            velocity.x = 0.0;
+            velocity.y = 0.0;
+            continue;
         }
+
+        let slow = status_effects
+            .intensity(StatusEffectTypes::Slow)
+            .clamp(0.0, 1.0);
+        velocity.x *= 1.0 - slow;
+        velocity.y *= 1.0 - slow;
     }
 }
 
@@ -231,17 +243,18 @@ fn apply_enemy_velocity(mut enemies: Query<(&mut Transform, &EnemyVelocity)>) {
 //     }
 // }
 
+// swarms crossing the screen are done once far away, the others are recycled
 fn check_enemy_too_far(
     mut commands: Commands,
     player: Query<&Transform, (With<Player>, Without<Enemy>)>,
-    enemies: Query<(Entity, &Transform), With<Enemy>>,
+    enemies: Query<(Entity, &Transform), With<EnemyFixedDirection>>,
 ) {
     let player = player.single();
     for (enemy, transform) in &enemies {
         if Vec2::distance(
             player.translation.truncate(),
             transform.translation.truncate(),
-        ) > 750.0
+        ) > ENEMY_RECYCLE_DISTANCE
         {
             commands.entity(enemy).despawn_recursive();
         }
@@ -249,16 +262,27 @@ fn check_enemy_too_far(
 }
 
 fn enemy_damage_player(
-    enemies: Query<(&CollidingEntities, &EnemyDamageOverTime), With<Enemy>>,
+    mut enemies: Query<
+        (
+            &CollidingEntities,
+            &EnemyDamageOverTime,
+            &EnemyType,
+            &mut AnimationController,
+        ),
+        With<Enemy>,
+    >,
     player: Query<Entity, With<Player>>,
     time: Res<Time>,
     mut player_received_damage_event: EventWriter<OnPlayerReceivedDamage>,
 ) {
     let player = player.single();
-    for (colliding_entities, damage) in enemies.iter() {
+    for (colliding_entities, damage, enemy_type, mut animation_controller) in &mut enemies {
         if colliding_entities.contains(player) {
+            // sheets with an attack clip swing at the player
+            animation_controller.trigger(AnimationStates::Attack);
             player_received_damage_event.send(OnPlayerReceivedDamage {
                 damage: damage.0 * time.delta_seconds(),
+                enemy_type: **enemy_type,
             });
         }
     }
@@ -266,32 +290,95 @@ fn enemy_damage_player(
 
 pub fn enemy_applied_impulse(
     mut commands: Commands,
-    mut enemies: Query<(Entity, &Transform), With<Enemy>>,
+    mut enemies: Query<(Entity, &Transform, &StatusEffects), KillableEnemy>,
     mut player: Query<&Transform, With<Player>>,
     mut eneny_hit_event: EventReader<OnEnemyHit>,
+    mut apply_status_effect: EventWriter<OnApplyStatusEffect>,
 ) {
     let player_transform = player.single_mut();
     for event in eneny_hit_event.read() {
         if let Some(impulse) = event.impulse {
-            if let Ok((enemy_entity, enemy_transform)) = enemies.get_mut(event.enemy_entity) {
+            if let Ok((enemy_entity, enemy_transform, status_effects)) =
+                enemies.get_mut(event.enemy_entity)
+            {
                 let direction: Vec2 = enemy_transform.translation.truncate()
                     - player_transform.translation.truncate();
+                let knockback_resistance = status_effects
+                    .intensity(StatusEffectTypes::KnockbackResistance)
+                    .clamp(0.0, 1.0);
                 commands.entity(enemy_entity).try_insert(ExternalImpulse {
-                    impulse: direction.normalize() * impulse,
+                    impulse: direction.normalize() * impulse * (1.0 - knockback_resistance),
                     torque_impulse: 0.0,
                 });
+                // a pushed enemy resists the next pushes for a moment, so piercing
+                // projectiles can't juggle it across the map
+                apply_status_effect.send(OnApplyStatusEffect {
+                    enemy_entity,
+                    effect_type: StatusEffectTypes::KnockbackResistance,
+                    value: 0.5,
+                    duration: 0.5,
+                });
             }
         }
     }
 }
 
 pub fn enemy_applied_received_damage(
-    mut enemies: Query<&mut Health, With<Enemy>>,
+    mut enemies: Query<
+        (
+            &Transform,
+            &mut Health,
+            &StatusEffects,
+            Option<&EnemyResistances>,
+        ),
+        KillableEnemy,
+    >,
     mut eneny_hit_event: EventReader<OnEnemyHit>,
+    mut enemy_received_damage: EventWriter<OnEnemyReceivedDamage>,
+    player_stats: Res<PlayerInGameStats>,
 ) {
     for event in eneny_hit_event.read() {
-        if let Ok(mut health) = enemies.get_mut(event.enemy_entity) {
-            **health -= event.damage;
+        if let Ok((transform, mut health, status_effects, resistances)) =
+            enemies.get_mut(event.enemy_entity)
+        {
+            let damage_type = event.projectile_type.damage_type();
+
+            let is_critical = event.projectile_type.can_crit()
+                && rand::thread_rng().gen_range(0.0..1.0) < player_stats.crit_chance;
+            let critical_multiplier = if is_critical {
+                player_stats.crit_multiplier
+            } else {
+                1.0
+            };
+
+            let (resistance, vulnerability) = match event.projectile_type.ignores_resistances() {
+                true => (0.0, 0.0),
+                false => (
+                    resistances
+                        .and_then(|resistances| resistances.get(&damage_type))
+                        .copied()
+                        .unwrap_or(0.0),
+                    status_effects.intensity(StatusEffectTypes::Vulnerability),
+                ),
+            };
+
+            let damage =
+                (event.damage * critical_multiplier * (1.0 - resistance) * (1.0 + vulnerability))
+                    .max(0.0);
+
+            // only the hit bringing the enemy below 0 counts as the kill
+            let is_killing_blow = **health > 0.0 && **health - damage <= 0.0;
+            **health -= damage;
+
+            enemy_received_damage.send(OnEnemyReceivedDamage {
+                damage,
+                enemy_entity: event.enemy_entity,
+                position: transform.translation,
+                damage_type,
+                is_critical,
+                is_killing_blow,
+                projectile_type: event.projectile_type,
+            });
         }
     }
 }
@@ -305,13 +392,16 @@ pub fn enemy_death_check(
             &Health,
             Option<&EnemyExperienceDrop>,
             Option<&EnemyBossDrop>,
+            Option<&EnemyDrops>,
         ),
         With<Enemy>,
     >,
     mut enemy_died: EventWriter<OnEnemyDied>,
     mut enemy_boss_died: EventWriter<OnEnemyBossDied>,
+    mut spawn_pickup: EventWriter<OnSpawnPickup>,
+    player_stats: Res<PlayerInGameStats>,
 ) {
-    for (entity, transform, health, experience, boss_drop) in &mut enemies {
+    for (entity, transform, health, experience, boss_drop, drops) in &mut enemies {
         if health.0 <= 0.0 {
             if let Some(experience) = experience {
                 enemy_died.send(OnEnemyDied {
@@ -324,7 +414,26 @@ pub fn enemy_death_check(
                     position: transform.translation.clone(),
                 });
             }
-            commands.entity(entity).despawn_recursive();
+            if let Some(drops) = drops {
+                for drop in drops.iter() {
+                    if drop.roll(&player_stats) {
+                        spawn_pickup.send(OnSpawnPickup {
+                            pickup_type: drop.pickup_type,
+                            position: transform.translation,
+                        });
+                    }
+                }
+            }
+            // not an enemy anymore, enemy systems and projectiles ignore it while it fades away
+            commands
+                .entity(entity)
+                .remove::<(Enemy, EnemyVelocity, EnemyHitFlash)>()
+                .insert((
+                    EnemyDying {
+                        timer: Timer::from_seconds(ENEMY_DEATH_DURATION, TimerMode::Once),
+                    },
+                    ColliderDisabled,
+                ));
         }
     }
 }
@@ -7,8 +7,6 @@ use bevy_rapier2d::prelude::*;
 pub struct EnemyBundle {
     pub sprite_bundle: SpriteBundle,
     pub texture_atlas: TextureAtlas,
-    pub animation_indices: AnimationIndices,
-    pub animation_timer: AnimationTimer,
     pub rigid_body: RigidBody,
     pub locked_axes: LockedAxes,
     pub damping: Damping,
@@ -23,6 +21,7 @@ pub struct EnemyBundle {
     pub enemy_speed: EnemySpeed,
     pub enemy_velocity: EnemyVelocity,
     pub enemy_damage_overtime: EnemyDamageOverTime,
+    pub status_effects: StatusEffects,
 }
 
 impl Default for EnemyBundle {
@@ -30,12 +29,6 @@ impl Default for EnemyBundle {
         Self {
             sprite_bundle: Default::default(),
             texture_atlas: Default::default(),
-            animation_indices: AnimationIndices {
-                first: 0,
-                last: 0,
-                is_repeating: true,
-            },
-            animation_timer: AnimationTimer(Timer::from_seconds(0.1, TimerMode::Repeating)),
             rigid_body: RigidBody::Dynamic,
             locked_axes: LockedAxes::ROTATION_LOCKED_Z,
             damping: Damping {
@@ -45,7 +38,7 @@ impl Default for EnemyBundle {
             collider: Default::default(),
             collision_group: CollisionGroups::new(
                 ENEMY_GROUP,
-                PLAYER_GROUP | ENEMY_GROUP | PROJECTILE_GROUP,
+                PLAYER_GROUP | ENEMY_GROUP | PROJECTILE_GROUP | OBSTACLE_GROUP,
             ),
             active_events: ActiveEvents::COLLISION_EVENTS,
             active_collision_types: Default::default(),
@@ -56,6 +49,7 @@ impl Default for EnemyBundle {
             enemy_speed: EnemySpeed(25.0),
             enemy_velocity: EnemyVelocity(Vec2::new(0.0, 0.0)),
             enemy_damage_overtime: EnemyDamageOverTime(10.0),
+            status_effects: StatusEffects::default(),
         }
     }
 }
@@ -0,0 +1,116 @@
+use crate::components::*;
+use crate::constants::{DEATH_EFFECT_Z_INDEX, ENEMY_HIT_FLASH_DURATION};
+use crate::pools::pool::TextureAtlasLayouts;
+use bevy::prelude::*;
+
+pub struct EnemyDeathPlugin;
+
+impl Plugin for EnemyDeathPlugin {
+    fn build(&self, app: &mut App) {
+        app.add_systems(
+            Update,
+            (
+                enemy_hit_flash,
+                enemy_hit_flash_tick,
+                spawn_death_effect,
+                enemy_dying,
+            )
+                .run_if(in_state(GameState::Gameplay)),
+        );
+    }
+}
+
+fn enemy_hit_flash(
+    mut commands: Commands,
+    mut enemy_hit: EventReader<OnEnemyHit>,
+    mut enemies: Query<&mut AnimationController, With<Enemy>>,
+) {
+    for event in enemy_hit.read() {
+        // a new hit restarts the flash, sheets with a hurt clip play it too
+        if let Ok(mut animation_controller) = enemies.get_mut(event.enemy_entity) {
+            animation_controller.trigger(AnimationStates::Hurt);
+            commands
+                .entity(event.enemy_entity)
+                .try_insert(EnemyHitFlash(Timer::from_seconds(
+                    ENEMY_HIT_FLASH_DURATION,
+                    TimerMode::Once,
+                )));
+        }
+    }
+}
+
+fn enemy_hit_flash_tick(
+    mut commands: Commands,
+    mut enemies: Query<(Entity, &mut EnemyHitFlash)>,
+    time: Res<Time>,
+) {
+    for (entity, mut hit_flash) in &mut enemies {
+        hit_flash.tick(time.delta());
+        if hit_flash.finished() {
+            commands.entity(entity).remove::<EnemyHitFlash>();
+        }
+    }
+}
+
+// Red tint, fade out and squash, then gone
+fn enemy_dying(
+    mut commands: Commands,
+    mut enemies: Query<(Entity, &mut EnemyDying, &mut Sprite, &mut Transform)>,
+    time: Res<Time>,
+) {
+    for (entity, mut dying, mut sprite, mut transform) in &mut enemies {
+        dying.timer.tick(time.delta());
+        if dying.timer.finished() {
+            commands.entity(entity).despawn_recursive();
+            continue;
+        }
+
+        let progress = dying.timer.fraction();
+        sprite.color = Color::rgba(1.0, 0.4, 0.4, 1.0 - progress);
+        transform.scale.y = 1.0 - progress * 0.5;
+    }
+}
+
+fn spawn_death_effect(
+    mut commands: Commands,
+    asset_server: Res<AssetServer>,
+    mut texture_atlas_layouts: TextureAtlasLayouts,
+    enemies_resource: Res<EnemiesResource>,
+    mut enemies: Query<(&Transform, &EnemyType, &mut AnimationController), Added<EnemyDying>>,
+) {
+    for (transform, enemy_type, mut animation_controller) in &mut enemies {
+        animation_controller.trigger(AnimationStates::Die);
+
+        let Some(death_effect) = &enemies_resource.get(enemy_type.0).death_effect else {
+            continue;
+        };
+
+        let sheet = texture_atlas_layouts.aseprite(&death_effect.animation_path);
+        let mut animation = SpriteAnimation::new(sheet.clone(), &death_effect.clip);
+        // a clip missing from the sheet never finishes, the effect would never be despawned
+        if !animation.play(&death_effect.clip) {
+            continue;
+        }
+
+        commands.spawn((
+            SpriteSheetBundle {
+                texture: asset_server.load(&sheet.texture_path),
+                atlas: TextureAtlas {
+                    layout: sheet.layout.clone(),
+                    index: animation.index(),
+                },
+                transform: Transform::from_translation(
+                    transform
+                        .translation
+                        .truncate()
+                        .extend(DEATH_EFFECT_Z_INDEX),
+                )
+                .with_scale(Vec3::splat(death_effect.scale)),
+                ..default()
+            },
+            animation,
+            AnimationFinishedAction::Despawn,
+            Name::new("Death effect"),
+        ));
+    }
+}
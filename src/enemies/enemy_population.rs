@@ -0,0 +1,75 @@
+use crate::components::*;
+use crate::constants::{ENEMY_RECYCLE_DISTANCE, SCREEN_HEIGHT, SCREEN_WIDTH};
+use crate::enemies::enemy::spawn_enemy;
+use crate::math_utils::get_random_position_outside_screen;
+use bevy::prelude::*;
+
+pub struct EnemyPopulationPlugin;
+
+impl Plugin for EnemyPopulationPlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<EnemyPopulation>();
+        app.add_systems(
+            Update,
+            (
+                count_enemy_population.before(spawn_enemy),
+                recycle_far_enemies.after(spawn_enemy),
+            )
+                .run_if(in_state(GameState::Gameplay)),
+        );
+    }
+}
+
+// swarms leave on their own and the reaper never goes away
+type RecyclableEnemy = (
+    With<Enemy>,
+    Without<EnemyFixedDirection>,
+    Without<EnemyUnkillable>,
+);
+
+fn count_enemy_population(
+    mut population: ResMut<EnemyPopulation>,
+    enemies: Query<&EnemyType, With<Enemy>>,
+) {
+    population.alive.clear();
+    population.total = 0;
+    for enemy_type in &enemies {
+        population.add(enemy_type.0);
+    }
+}
+
+// Far away enemies come back just outside the screen, they keep their health and experience
+fn recycle_far_enemies(
+    mut population: ResMut<EnemyPopulation>,
+    player: Query<&Transform, (With<Player>, Without<Enemy>)>,
+    mut enemies: Query<(&mut Transform, &EnemyType), RecyclableEnemy>,
+    spawn_exclusion_zones: Res<SpawnExclusionZones>,
+) {
+    let player_position = player.single().translation.truncate();
+    let mut overflow = std::mem::take(&mut population.overflow);
+    // never move an enemy the player can see
+    let screen_radius = Vec2::new(SCREEN_WIDTH as f32, SCREEN_HEIGHT as f32).length() / 2.0;
+
+    let mut off_screen: Vec<(Mut<Transform>, EnemyTypes, f32)> = enemies
+        .iter_mut()
+        .filter_map(|(transform, enemy_type)| {
+            let distance = player_position.distance(transform.translation.truncate());
+            (distance > screen_radius).then_some((transform, enemy_type.0, distance))
+        })
+        .collect();
+    // farthest first
+    off_screen.sort_by(|a, b| b.2.total_cmp(&a.2));
+
+    for (mut transform, enemy_type, distance) in off_screen {
+        let requested = overflow.get_mut(&enemy_type).filter(|count| **count > 0);
+        match requested {
+            Some(count) => *count -= 1,
+            None if distance <= ENEMY_RECYCLE_DISTANCE => continue,
+            None => {}
+        }
+
+        transform.translation =
+            get_random_position_outside_screen(player_position, &spawn_exclusion_zones)
+                .extend(transform.translation.z);
+    }
+}
@@ -40,6 +40,7 @@ fn spawn_boss_gem_on_enemy_death(
             ActiveCollisionTypes::STATIC_STATIC,
             CollidingEntities::default(),
             GemBoss,
+            OffScreenIndicator(IndicatorTypes::BossGem),
             Name::new("Gem Boss"),
         ));
     }
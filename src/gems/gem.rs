@@ -1,7 +1,11 @@
 use crate::components::*;
 use crate::constants::*;
+use crate::math_utils::is_position_outside_screen;
 use bevy::prelude::*;
+use bevy::time::common_conditions::on_timer;
+use bevy::utils::{HashMap, HashSet};
 use bevy_rapier2d::prelude::*;
+use std::time::Duration;
 
 pub struct GemsPlugin;
 
@@ -14,9 +18,17 @@ impl Plugin for GemsPlugin {
                 gem_retrieve_by_user,
                 move_gem_attracted_by_player,
                 gem_hit_player_pickup_radius,
+                update_gem_tier_sprite,
+                update_gem_overflow_sprite,
             )
                 .run_if(in_state(GameState::Gameplay)),
         );
+        app.add_systems(
+            Update,
+            merge_gems
+                .run_if(in_state(GameState::Gameplay))
+                .run_if(on_timer(Duration::from_secs_f32(0.5))),
+        );
     }
 }
 
@@ -24,11 +36,29 @@ fn spawn_gem_on_enemy_death(
     mut commands: Commands,
     asset_server: Res<AssetServer>,
     mut enemy_died: EventReader<OnEnemyDied>,
+    mut gem_pool: ResMut<GemPool>,
 ) {
     for event in enemy_died.read() {
+        // reuse a collected gem, its collider is already built
+        if let Some(gem_entity) = gem_pool.free.pop() {
+            if let Some(mut gem_commands) = commands.get_entity(gem_entity) {
+                gem_commands
+                    .insert((
+                        Transform::from_xyz(event.position.x, event.position.y, GEM_Z_INDEX),
+                        Visibility::Inherited,
+                        Gem {
+                            experience: event.experience,
+                        },
+                    ))
+                    .remove::<ColliderDisabled>();
+                continue;
+            }
+        }
+
         commands.spawn((
             SpriteBundle {
-                texture: asset_server.load("gem.png"),
+                texture: asset_server
+                    .load(GemTiers::from_experience(event.experience).texture_path()),
                 transform: Transform::from_xyz(event.position.x, event.position.y, GEM_Z_INDEX),
                 ..default()
             },
@@ -76,6 +106,7 @@ fn gem_retrieve_by_user(
     >,
     player: Query<Entity, With<Player>>,
     mut collect_experience: EventWriter<OnCollectExperience>,
+    mut gem_pool: ResMut<GemPool>,
 ) {
     let player = player.single();
     for (gem_entity, gem, colliding_entities) in &mut gems {
@@ -84,8 +115,8 @@ fn gem_retrieve_by_user(
                 experience: gem.experience,
             });
 
-            // delete gem
-            commands.entity(gem_entity).despawn_recursive();
+            // back to the pool
+            gem_pool.release(&mut commands, gem_entity);
         }
     }
 }
@@ -105,3 +136,84 @@ fn move_gem_attracted_by_player(
         gem_transform.translation.y -= direction.y * time.delta_seconds() * 200.0;
     }
 }
+
+fn update_gem_tier_sprite(
+    mut gems: Query<(&Gem, &mut Handle<Image>), Changed<Gem>>,
+    asset_server: Res<AssetServer>,
+) {
+    for (gem, mut texture) in &mut gems {
+        *texture = asset_server.load(GemTiers::from_experience(gem.experience).texture_path());
+    }
+}
+
+fn update_gem_overflow_sprite(mut gems: Query<&mut Transform, Added<GemOverflow>>) {
+    for mut transform in &mut gems {
+        transform.scale = Vec3::splat(1.5);
+    }
+}
+
+// gems neither attracted by the player nor waiting in the pool
+type GemOnGround = (Without<ColliderDisabled>, Without<GemIsAttracted>);
+
+// Keep the amount of gems bounded: far away gems go into a single overflow gem
+// and gems close to each other on screen are merged together
+fn merge_gems(
+    mut commands: Commands,
+    mut gems: Query<(Entity, &mut Gem, &Transform, Has<GemOverflow>), GemOnGround>,
+    player: Query<&Transform, (With<Player>, Without<Gem>)>,
+    mut gem_pool: ResMut<GemPool>,
+) {
+    if gems.iter().count() <= MAX_GEMS_BEFORE_MERGE {
+        return;
+    }
+    let player_position = player.single().translation.truncate();
+
+    let mut overflow_gem: Option<Entity> = gems
+        .iter()
+        .find(|(_, _, _, is_overflow)| *is_overflow)
+        .map(|(entity, _, _, _)| entity);
+    let mut merged_experience: HashMap<Entity, u32> = HashMap::new();
+    let mut merged_gems: HashSet<Entity> = HashSet::new();
+    let mut cells: HashMap<IVec2, Entity> = HashMap::new();
+
+    for (entity, gem, transform, is_overflow) in &gems {
+        if is_overflow {
+            continue;
+        }
+        let position = transform.translation.truncate();
+
+        let target = if is_position_outside_screen(player_position, position, GEM_MERGE_RADIUS) {
+            match overflow_gem {
+                Some(overflow_gem) => overflow_gem,
+                None => {
+                    // first far away gem becomes the overflow one
+                    overflow_gem = Some(entity);
+                    commands.entity(entity).insert(GemOverflow);
+                    continue;
+                }
+            }
+        } else {
+            let cell = (position / GEM_MERGE_RADIUS).floor().as_ivec2();
+            match cells.get(&cell) {
+                Some(cell_gem) => *cell_gem,
+                None => {
+                    cells.insert(cell, entity);
+                    continue;
+                }
+            }
+        };
+
+        *merged_experience.entry(target).or_default() += gem.experience;
+        merged_gems.insert(entity);
+    }
+
+    for (target, experience) in merged_experience {
+        if let Ok((_, mut gem, _, _)) = gems.get_mut(target) {
+            gem.experience += experience;
+        }
+    }
+
+    for entity in merged_gems {
+        gem_pool.release(&mut commands, entity);
+    }
+}
@@ -1,5 +1,8 @@
 use crate::components::*;
 use bevy::prelude::*;
+use rand::distributions::{Distribution, WeightedIndex};
+use rand::seq::SliceRandom;
+use rand::Rng;
 use std::collections::HashMap;
 use std::fs;
 
@@ -51,6 +54,25 @@ fn setup_loot_table(mut commands: Commands) {
     commands.insert_resource(loot_table);
 }
 
+// Pick a rarity from the weighted loot table then an item of that rarity.
+// Unique items are removed from the table once taken, so the pick can fail.
+pub fn roll_item(loot_table: &LootTable) -> Option<(String, Rarity)> {
+    let dist = WeightedIndex::new(loot_table.weighted_rarity.iter().map(|item| item.1)).unwrap();
+    let mut rarity = loot_table.weighted_rarity[dist.sample(&mut rand::thread_rng())].0;
+
+    // has a small chance to be a cursed item
+    if rand::thread_rng().gen_range(0.0..100.0) < 1.0 {
+        rarity = loot_table.weighted_rarity[5].0;
+    }
+
+    loot_table
+        .item_by_rarity
+        .get(&rarity)
+        .unwrap()
+        .choose(&mut rand::thread_rng())
+        .map(|item_key| (item_key.clone(), rarity))
+}
+
 fn trigger_item(
     mut item_event: EventReader<OnItemPickup>,
     mut player_stats: ResMut<PlayerInGameStats>,
@@ -114,6 +136,13 @@ fn trigger_item(
                 PlayerBaseStatsType::ExtraLife => {
                     player_stats.extra_life += effect.value as u32;
                 }
+                PlayerBaseStatsType::CritChance => {
+                    // additive, 0.05 is +5% chance
+                    player_stats.crit_chance += effect.value;
+                }
+                PlayerBaseStatsType::CritMultiplier => {
+                    player_stats.crit_multiplier += effect.value;
+                }
             }
         }
 
@@ -1,12 +1,21 @@
 pub mod animations;
+pub mod audio;
 pub mod cameras;
 pub mod components;
 pub mod constants;
 pub mod enemies;
 pub mod gems;
 pub mod items;
+pub mod maps;
 pub mod math_utils;
+pub mod pickups;
 pub mod players;
+pub mod pools;
+pub mod props;
+pub mod settings;
+pub mod stages;
+pub mod statistics;
+pub mod status_effects;
 pub mod stepping;
 pub mod ui;
 pub mod waves;
@@ -2,31 +2,50 @@ use bevy::prelude::*;
 
 use bevy::diagnostic::FrameTimeDiagnosticsPlugin;
 use bevy::input::common_conditions::input_toggle_active;
-use bevy::window::WindowMode;
 use bevy_inspector_egui::quick::WorldInspectorPlugin;
 use bevy_pixel_camera::PixelCameraPlugin;
 use bevy_rapier2d::prelude::*;
 
 use yet_another_vampire_survivor_clone::animations::animation::AnimationSimplePlugin;
+use yet_another_vampire_survivor_clone::audio::music::MusicPlugin;
+use yet_another_vampire_survivor_clone::audio::sound::SoundPlugin;
 use yet_another_vampire_survivor_clone::cameras::camera::PlayerCameraPlugin;
 use yet_another_vampire_survivor_clone::components::*;
 use yet_another_vampire_survivor_clone::constants::{SCREEN_HEIGHT, SCREEN_WIDTH};
 use yet_another_vampire_survivor_clone::enemies::enemy::EnemyPlugin;
+use yet_another_vampire_survivor_clone::enemies::enemy_death::EnemyDeathPlugin;
+use yet_another_vampire_survivor_clone::enemies::enemy_population::EnemyPopulationPlugin;
 use yet_another_vampire_survivor_clone::gems::boss_gem::GemsBossPlugin;
 use yet_another_vampire_survivor_clone::gems::gem::GemsPlugin;
 use yet_another_vampire_survivor_clone::items::item::ItemsPlugin;
+use yet_another_vampire_survivor_clone::maps::map::MapPlugin;
 use yet_another_vampire_survivor_clone::math_utils::get_random_position_in_screen;
+use yet_another_vampire_survivor_clone::pickups::pickup::PickupPlugin;
 use yet_another_vampire_survivor_clone::players::player::PlayerPlugin;
+use yet_another_vampire_survivor_clone::players::player_aim::PlayerAimPlugin;
+use yet_another_vampire_survivor_clone::pools::pool::PoolPlugin;
+use yet_another_vampire_survivor_clone::props::prop::PropPlugin;
+use yet_another_vampire_survivor_clone::settings::settings::SettingsPlugin;
+use yet_another_vampire_survivor_clone::stages::difficulty::DifficultyPlugin;
+use yet_another_vampire_survivor_clone::stages::stage::StagePlugin;
+use yet_another_vampire_survivor_clone::statistics::meta_progression::MetaProgressionPlugin;
+use yet_another_vampire_survivor_clone::statistics::run_statistics::RunStatisticsPlugin;
+use yet_another_vampire_survivor_clone::status_effects::status_effect::StatusEffectPlugin;
 use yet_another_vampire_survivor_clone::ui::ui_choose_weapon::UiChooseWeaponPlugin;
 use yet_another_vampire_survivor_clone::ui::ui_enemy::UiEnemyPlugin;
 use yet_another_vampire_survivor_clone::ui::ui_fps::UiFPSPlugin;
+use yet_another_vampire_survivor_clone::ui::ui_game_over::UiGameOverPlugin;
 use yet_another_vampire_survivor_clone::ui::ui_global_timer::UiGlobalTimerPlugin;
 use yet_another_vampire_survivor_clone::ui::ui_level_up::UiLevelUpPlugin;
 use yet_another_vampire_survivor_clone::ui::ui_main_menu::UiMainMenuPlugin;
+use yet_another_vampire_survivor_clone::ui::ui_minimap::UiMinimapPlugin;
+use yet_another_vampire_survivor_clone::ui::ui_off_screen_indicator::UiOffScreenIndicatorPlugin;
+use yet_another_vampire_survivor_clone::ui::ui_pause::UiPausePlugin;
 use yet_another_vampire_survivor_clone::ui::ui_player::UiPlayerPlugin;
+use yet_another_vampire_survivor_clone::ui::ui_settings::UiSettingsPlugin;
+use yet_another_vampire_survivor_clone::ui::ui_stage_select::UiStageSelectPlugin;
 use yet_another_vampire_survivor_clone::ui::ui_update_weapon_up::UiUpdateWeaponPlugin;
 use yet_another_vampire_survivor_clone::waves::waves::WavesPlugin;
-use yet_another_vampire_survivor_clone::waves::waves_map_1::WavesMap1Plugin;
 use yet_another_vampire_survivor_clone::weapons::weapons::WeaponsPlugin;
 
 fn main() {
@@ -52,11 +71,21 @@ fn main() {
         .add_event::<OnEnemyBossDied>()
         .add_event::<OnCollectExperience>()
         .add_event::<OnEnemyHit>()
+        .add_event::<OnEnemyReceivedDamage>()
         .add_event::<OnPlayerReceivedDamage>()
         .add_event::<OnSpawnEnemy>()
         .add_event::<OnItemPickup>()
         .add_event::<OnUpgradePickup>()
         .add_event::<OnWeaponPickup>()
+        .add_event::<OnApplyStatusEffect>()
+        .add_event::<OnSpawnPickup>()
+        .add_event::<OnPickupCollected>()
+        .add_event::<OnPropHit>()
+        .add_event::<OnPropDestroyed>()
+        .add_event::<OnAnimationEvent>()
+        .add_event::<OnAnimationFinished>()
+        .add_event::<OnPlaySound>()
+        .add_event::<OnCameraShake>()
         // FPS plugin
         .add_plugins(FrameTimeDiagnosticsPlugin::default())
         .add_plugins(UiFPSPlugin)
@@ -71,38 +100,65 @@ fn main() {
         .add_plugins(
             WorldInspectorPlugin::default().run_if(input_toggle_active(false, KeyCode::Escape)),
         )
+        // Pools and cached assets
+        .add_plugins(PoolPlugin)
+        // Stages and map
+        .add_plugins(StagePlugin)
+        .add_plugins(DifficultyPlugin)
+        .add_plugins(MapPlugin)
         // Camera pixel plugin
         .add_plugins(PixelCameraPlugin)
         .add_plugins(PlayerCameraPlugin)
         // Player plugin
         .add_plugins(PlayerPlugin)
+        .add_plugins(PlayerAimPlugin)
         // items
         .add_plugins(ItemsPlugin)
         // Waves
         .add_plugins(WavesPlugin)
-        .add_plugins(WavesMap1Plugin) // Temp
         // Enemies plugin
         .add_plugins(EnemyPlugin)
+        .add_plugins(EnemyPopulationPlugin)
+        .add_plugins(EnemyDeathPlugin)
+        // Status effects
+        .add_plugins(StatusEffectPlugin)
+        // Statistics
+        .add_plugins(RunStatisticsPlugin)
+        .add_plugins(MetaProgressionPlugin)
         // UI
         .add_plugins(UiEnemyPlugin)
+        .add_plugins(UiOffScreenIndicatorPlugin)
+        .add_plugins(UiMinimapPlugin)
         .add_plugins(UiMainMenuPlugin)
+        .add_plugins(UiStageSelectPlugin)
         .add_plugins(UiPlayerPlugin)
+        .add_plugins(UiSettingsPlugin)
         .add_plugins(UiLevelUpPlugin)
         .add_plugins(UiGlobalTimerPlugin)
         .add_plugins(UiUpdateWeaponPlugin)
         .add_plugins(UiChooseWeaponPlugin)
+        .add_plugins(UiPausePlugin)
+        .add_plugins(UiGameOverPlugin)
         // animation
         .add_plugins(AnimationSimplePlugin)
+        // Audio
+        .add_plugins(SoundPlugin)
+        .add_plugins(MusicPlugin)
+        // Settings, after the audio so the volumes exist
+        .add_plugins(SettingsPlugin)
         // gems
         .add_plugins(GemsPlugin)
         .add_plugins(GemsBossPlugin)
+        // pickups
+        .add_plugins(PickupPlugin)
+        // props
+        .add_plugins(PropPlugin)
         // Weapons
         .add_plugins(WeaponsPlugin)
         // Setup
         // .add_systems(Startup, setup)
         // test
         .insert_resource(Time::<Fixed>::from_hz(64.0))
-        .add_systems(Startup, background)
         .add_systems(Update, debug)
         // .add_plugins(
         //     SteppingPlugin::default()
@@ -113,39 +169,8 @@ fn main() {
         .run();
 }
 
-fn background(mut commands: Commands, asset_server: Res<AssetServer>) {
-    let texture = asset_server.load("map_1/png/background_level_1.png");
-
-    let parent = commands
-        .spawn((
-            TransformBundle {
-                local: Default::default(),
-                global: Default::default(),
-            },
-            InheritedVisibility::default(),
-            Name::new("Background parent"),
-        ))
-        .id();
-
-    for i in -3..3 {
-        for y in -3..3 {
-            let child = commands
-                .spawn((
-                    SpriteBundle {
-                        texture: texture.clone(),
-                        transform: Transform::from_xyz(i as f32 * 1024.0, y as f32 * 1024.0, -1.0),
-                        ..default()
-                    },
-                    Name::new("Background"),
-                ))
-                .id();
-            commands.entity(parent).push_children(&[child]);
-        }
-    }
-}
-
 fn debug(
-    mut windows: Query<&mut Window>,
+    mut settings: ResMut<GameSettings>,
     keyboard_input: Res<ButtonInput<KeyCode>>,
     mut player_weapons: ResMut<PlayerWeapons>,
     mut weapon_upgrades: ResMut<PlayerUpgradeWeapons>,
@@ -195,16 +220,16 @@ fn debug(
     if keyboard_input.just_pressed(KeyCode::KeyP) {
         spawn_enemy.send(OnSpawnEnemy {
             enemy_types: EnemyTypes::BossWolf,
+            position: None,
+            direction: None,
         });
     }
 
-    let mut window = windows.single_mut();
-
     if keyboard_input.just_pressed(KeyCode::F1) {
-        window.mode = WindowMode::BorderlessFullscreen;
+        settings.is_fullscreen = true;
     }
     if keyboard_input.just_pressed(KeyCode::F2) {
-        window.mode = WindowMode::Windowed;
+        settings.is_fullscreen = false;
     }
 
     if keyboard_input.pressed(KeyCode::KeyG) {
@@ -0,0 +1,2 @@
+pub mod ldtk;
+pub mod map;
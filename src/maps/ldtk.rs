@@ -0,0 +1,142 @@
+use bevy::prelude::*;
+use serde::Deserialize;
+use std::fs;
+
+// Only the part of the LDtk json format the game needs
+// https://ldtk.io/json/
+
+#[derive(Deserialize, Debug)]
+pub struct LdtkProject {
+    pub defs: LdtkDefinitions,
+    pub levels: Vec<LdtkLevel>,
+}
+
+#[derive(Deserialize, Debug)]
+pub struct LdtkDefinitions {
+    pub tilesets: Vec<LdtkTilesetDefinition>,
+}
+
+#[derive(Deserialize, Debug)]
+#[serde(rename_all = "camelCase")]
+pub struct LdtkTilesetDefinition {
+    pub uid: i64,
+    // null for embedded tilesets
+    pub rel_path: Option<String>,
+    pub px_wid: u32,
+    pub px_hei: u32,
+    pub padding: u32,
+    pub spacing: u32,
+}
+
+#[derive(Deserialize, Debug)]
+#[serde(rename_all = "camelCase")]
+pub struct LdtkLevel {
+    pub identifier: String,
+    pub px_wid: u32,
+    pub px_hei: u32,
+    // null when levels are saved in separate files
+    pub layer_instances: Option<Vec<LdtkLayerInstance>>,
+}
+
+#[derive(Deserialize, Debug)]
+#[serde(rename_all = "camelCase")]
+pub struct LdtkLayerInstance {
+    #[serde(rename = "__identifier")]
+    pub identifier: String,
+    #[serde(rename = "__type")]
+    pub layer_type: String,
+    #[serde(rename = "__cWid")]
+    pub c_wid: u32,
+    #[serde(rename = "__gridSize")]
+    pub grid_size: u32,
+    #[serde(rename = "__opacity")]
+    pub opacity: f32,
+    #[serde(rename = "__pxTotalOffsetX")]
+    pub px_total_offset_x: i32,
+    #[serde(rename = "__pxTotalOffsetY")]
+    pub px_total_offset_y: i32,
+    #[serde(rename = "__tilesetDefUid")]
+    pub tileset_def_uid: Option<i64>,
+    pub visible: bool,
+    pub int_grid_csv: Vec<i32>,
+    pub grid_tiles: Vec<LdtkTile>,
+    pub auto_layer_tiles: Vec<LdtkTile>,
+    pub entity_instances: Vec<LdtkEntityInstance>,
+}
+
+impl LdtkLayerInstance {
+    // Tiles and AutoLayer layers use a different list, IntGrid layers can have both
+    pub fn tiles(&self) -> impl Iterator<Item = &LdtkTile> {
+        self.grid_tiles.iter().chain(self.auto_layer_tiles.iter())
+    }
+
+    // Top left pixel of every non empty IntGrid cell
+    pub fn int_grid_cells(&self) -> impl Iterator<Item = IVec2> + '_ {
+        self.int_grid_csv
+            .iter()
+            .enumerate()
+            .filter(|(_, value)| **value != 0)
+            .map(|(index, _)| {
+                IVec2::new(
+                    (index as u32 % self.c_wid * self.grid_size) as i32,
+                    (index as u32 / self.c_wid * self.grid_size) as i32,
+                )
+            })
+    }
+}
+
+#[derive(Deserialize, Debug)]
+pub struct LdtkTile {
+    // pixel position in the layer
+    pub px: [i32; 2],
+    // pixel position in the tileset
+    pub src: [i32; 2],
+    // bit 0 is flip x, bit 1 is flip y
+    pub f: u8,
+    #[serde(default = "default_tile_alpha")]
+    pub a: f32,
+}
+
+fn default_tile_alpha() -> f32 {
+    1.0
+}
+
+#[derive(Deserialize, Debug)]
+pub struct LdtkEntityInstance {
+    #[serde(rename = "__identifier")]
+    pub identifier: String,
+    #[serde(rename = "__pivot")]
+    pub pivot: [f32; 2],
+    pub px: [i32; 2],
+    pub width: u32,
+    pub height: u32,
+}
+
+impl LdtkEntityInstance {
+    // Rect of the entity in LDtk pixel space (y down)
+    pub fn rect(&self) -> Rect {
+        let min = Vec2::new(
+            self.px[0] as f32 - self.pivot[0] * self.width as f32,
+            self.px[1] as f32 - self.pivot[1] * self.height as f32,
+        );
+        Rect::from_corners(min, min + Vec2::new(self.width as f32, self.height as f32))
+    }
+}
+
+impl LdtkProject {
+    pub fn load(path: &str) -> LdtkProject {
+        let data = fs::read_to_string(path).unwrap();
+        serde_json::from_str(&data).unwrap()
+    }
+
+    pub fn level(&self, identifier: &str) -> &LdtkLevel {
+        self.levels
+            .iter()
+            .find(|level| level.identifier == identifier)
+            .unwrap_or_else(|| panic!("LDtk level {} not found", identifier))
+    }
+
+    pub fn tileset(&self, uid: i64) -> Option<&LdtkTilesetDefinition> {
+        self.defs.tilesets.iter().find(|tileset| tileset.uid == uid)
+    }
+}
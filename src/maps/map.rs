@@ -0,0 +1,380 @@
+use crate::components::*;
+use crate::constants::*;
+use crate::maps::ldtk::{LdtkLayerInstance, LdtkProject, LdtkTilesetDefinition};
+use crate::pools::pool::TextureAtlasLayouts;
+use bevy::prelude::*;
+use bevy_rapier2d::prelude::*;
+use rand::rngs::StdRng;
+use rand::seq::SliceRandom;
+use rand::{Rng, SeedableRng};
+use std::fs;
+use std::path::Path;
+
+pub struct MapPlugin;
+
+impl Plugin for MapPlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<SpawnExclusionZones>();
+        app.init_resource::<MapLevel>();
+        app.init_resource::<MapChunks>();
+
+        // the map comes from the stage picked by the player
+        app.add_systems(OnExit(GameState::StageSelect), load_map);
+        app.add_systems(
+            Update,
+            (stream_map_chunks, build_map_chunks)
+                .chain()
+                .run_if(resource_exists::<MapData>),
+        );
+    }
+}
+
+fn load_map(
+    mut commands: Commands,
+    asset_server: Res<AssetServer>,
+    mut texture_atlas_layouts: TextureAtlasLayouts,
+    stages: Res<StagesResource>,
+    selected_stage: Res<SelectedStage>,
+    mut spawn_exclusion_zones: ResMut<SpawnExclusionZones>,
+) {
+    let stage = &stages.stages[selected_stage.0];
+    let data = fs::read_to_string(&stage.map_path).unwrap();
+    let map_data: MapData = ron::from_str(&data).unwrap();
+
+    let project = LdtkProject::load(&map_data.ldtk_path);
+    let level = project.level(&map_data.level);
+    let level_size = Vec2::new(level.px_wid as f32, level.px_hei as f32);
+
+    // tilesets are relative to the ldtk file, the asset server is relative to assets/
+    let ldtk_folder = Path::new(&map_data.ldtk_path)
+        .parent()
+        .and_then(|folder| folder.strip_prefix("assets").ok())
+        .unwrap_or(Path::new(""))
+        .to_path_buf();
+
+    // LDtk is y down with the origin on the top left corner, the level is centered on 0,0
+    let to_level_space = |px: Vec2, size: Vec2| -> Vec2 {
+        Vec2::new(
+            px.x + size.x / 2.0 - level_size.x / 2.0,
+            level_size.y / 2.0 - (px.y + size.y / 2.0),
+        )
+    };
+
+    let ground_tint = map_data
+        .ground_tint
+        .map(|[red, green, blue]| Color::rgb(red, green, blue))
+        .unwrap_or(Color::WHITE);
+
+    let mut tiles: Vec<MapTile> = Vec::new();
+    let mut obstacles: Vec<MapObstacle> = Vec::new();
+    let mut zones: Vec<Rect> = Vec::new();
+
+    let layers = level.layer_instances.as_deref().unwrap_or_default();
+    // first layer in LDtk is the one on top
+    for (depth, layer) in layers.iter().rev().enumerate() {
+        let layer_offset = Vec2::new(
+            layer.px_total_offset_x as f32,
+            layer.px_total_offset_y as f32,
+        );
+        let grid_size = Vec2::splat(layer.grid_size as f32);
+
+        if layer.visible {
+            if let Some(tileset) = layer
+                .tileset_def_uid
+                .and_then(|uid| project.tileset(uid))
+                .filter(|tileset| tileset.rel_path.is_some())
+            {
+                let texture: Handle<Image> =
+                    asset_server.load(ldtk_folder.join(tileset.rel_path.as_ref().unwrap()));
+                let (layout, columns) =
+                    tileset_layout(&mut texture_atlas_layouts, tileset, layer.grid_size);
+                let step = (layer.grid_size + tileset.spacing) as i32;
+
+                for tile in layer.tiles() {
+                    let column = (tile.src[0] - tileset.padding as i32) / step;
+                    let row = (tile.src[1] - tileset.padding as i32) / step;
+                    let px = Vec2::new(tile.px[0] as f32, tile.px[1] as f32) + layer_offset;
+                    tiles.push(MapTile {
+                        texture: texture.clone(),
+                        layout: layout.clone(),
+                        index: row as usize * columns + column as usize,
+                        position: to_level_space(px, grid_size)
+                            .extend(MAP_Z_INDEX + depth as f32 * 0.01),
+                        flip_x: tile.f & 1 != 0,
+                        flip_y: tile.f & 2 != 0,
+                        color: ground_tint.with_a(tile.a * layer.opacity),
+                    });
+                }
+            }
+        }
+
+        if let Some(obstacle_layer) = map_data
+            .obstacle_layers
+            .iter()
+            .find(|obstacle_layer| obstacle_layer.identifier == layer.identifier)
+        {
+            for px in obstacle_cells(layer) {
+                let position = to_level_space(px + layer_offset, grid_size);
+                let half_size = obstacle_layer.collider_size / 2.0;
+                obstacles.push(MapObstacle {
+                    position,
+                    half_size,
+                });
+                zones.push(Rect::from_center_half_size(
+                    position,
+                    half_size + map_data.spawn_exclusion_margin,
+                ));
+            }
+        }
+
+        // zones drawn by hand in the level
+        for entity in layer.entity_instances.iter() {
+            if entity.identifier == "SpawnExclusion" {
+                let rect = entity.rect();
+                zones.push(Rect::from_center_size(
+                    to_level_space(rect.min + layer_offset, rect.size()),
+                    rect.size(),
+                ));
+            }
+        }
+    }
+
+    // the player always starts at 0,0, move the level so it lands on the map start position
+    let level_offset = -map_data.player_start;
+    *spawn_exclusion_zones = SpawnExclusionZones {
+        level_size,
+        level_offset,
+        zones,
+    };
+
+    commands.insert_resource(MapLevel {
+        level_size,
+        level_offset,
+        tiles,
+        obstacles,
+    });
+    commands.insert_resource(map_data);
+}
+
+// Keep the chunks around the player loaded, the level is copied in each of them
+fn stream_map_chunks(
+    mut commands: Commands,
+    map_data: Res<MapData>,
+    map_level: Res<MapLevel>,
+    mut map_chunks: ResMut<MapChunks>,
+    player: Query<&Transform, With<Player>>,
+) {
+    let Ok(player) = player.get_single() else {
+        return;
+    };
+    let player_chunk = map_level.chunk_at(player.translation.truncate());
+
+    // one more chunk is kept before unloading to avoid reloading when walking on a border
+    map_chunks.loaded.retain(|chunk, chunk_entity| {
+        let distance = (*chunk - player_chunk).abs().max_element();
+        if distance > map_data.chunk_radius + 1 {
+            commands.entity(*chunk_entity).despawn_recursive();
+            return false;
+        }
+        true
+    });
+
+    for x in -map_data.chunk_radius..=map_data.chunk_radius {
+        for y in -map_data.chunk_radius..=map_data.chunk_radius {
+            let chunk = player_chunk + IVec2::new(x, y);
+            if map_chunks.loaded.contains_key(&chunk) {
+                continue;
+            }
+
+            let chunk_entity = spawn_chunk(&mut commands, &map_level, chunk);
+            map_chunks.loaded.insert(chunk, chunk_entity);
+        }
+    }
+}
+
+fn spawn_chunk(commands: &mut Commands, map_level: &MapLevel, chunk: IVec2) -> Entity {
+    let chunk_position = map_level.chunk_position(chunk);
+
+    commands
+        .spawn((
+            SpatialBundle::from_transform(Transform::from_translation(chunk_position.extend(0.0))),
+            MapChunk(chunk),
+            MapChunkBuild::default(),
+            Name::new(format!("Chunk {} {}", chunk.x, chunk.y)),
+        ))
+        .id()
+}
+
+// A chunk is over a thousand sprites, spawning them all at once hitches when crossing a border.
+// Chunks are filled a few hundred entities per frame, closest to the player first.
+fn build_map_chunks(
+    mut commands: Commands,
+    asset_server: Res<AssetServer>,
+    map_data: Res<MapData>,
+    map_level: Res<MapLevel>,
+    spawn_exclusion_zones: Res<SpawnExclusionZones>,
+    mut chunks: Query<(Entity, &MapChunk, &mut MapChunkBuild)>,
+    player: Query<&Transform, With<Player>>,
+) {
+    let Ok(player) = player.get_single() else {
+        return;
+    };
+    let player_chunk = map_level.chunk_at(player.translation.truncate());
+
+    let mut building: Vec<_> = chunks.iter_mut().collect();
+    building.sort_by_key(|(_, chunk, _)| (chunk.0 - player_chunk).abs().max_element());
+
+    let obstacle_count = map_level.obstacles.len();
+    let tile_count = map_level.tiles.len();
+    let mut budget = MAP_CHUNK_SPAWN_BUDGET;
+
+    for (chunk_entity, chunk, mut build) in building {
+        if budget == 0 {
+            break;
+        }
+        let chunk_position = map_level.chunk_position(chunk.0);
+
+        commands.entity(chunk_entity).with_children(|parent| {
+            // obstacles first, enemies and the player collide with them
+            while budget > 0 && build.next < obstacle_count {
+                spawn_obstacle(parent, &map_level.obstacles[build.next], chunk_position);
+                build.next += 1;
+                budget -= 1;
+            }
+            while budget > 0 && build.next < obstacle_count + tile_count {
+                spawn_tile(parent, &map_level.tiles[build.next - obstacle_count]);
+                build.next += 1;
+                budget -= 1;
+            }
+            if budget > 0 && build.next == obstacle_count + tile_count {
+                budget = budget.saturating_sub(spawn_decorations(
+                    parent,
+                    &asset_server,
+                    &map_data,
+                    &map_level,
+                    &spawn_exclusion_zones,
+                    chunk.0,
+                ));
+                build.next += 1;
+            }
+        });
+
+        if build.next > obstacle_count + tile_count {
+            commands.entity(chunk_entity).remove::<MapChunkBuild>();
+        }
+    }
+}
+
+fn spawn_tile(parent: &mut ChildBuilder, tile: &MapTile) {
+    parent.spawn(SpriteSheetBundle {
+        texture: tile.texture.clone(),
+        atlas: TextureAtlas {
+            layout: tile.layout.clone(),
+            index: tile.index,
+        },
+        sprite: Sprite {
+            flip_x: tile.flip_x,
+            flip_y: tile.flip_y,
+            color: tile.color,
+            ..default()
+        },
+        transform: Transform::from_translation(tile.position),
+        ..default()
+    });
+}
+
+fn spawn_obstacle(parent: &mut ChildBuilder, obstacle: &MapObstacle, chunk_position: Vec2) {
+    parent.spawn((
+        TransformBundle::from_transform(Transform::from_translation(obstacle.position.extend(0.0))),
+        RigidBody::Fixed,
+        Collider::cuboid(obstacle.half_size.x, obstacle.half_size.y),
+        CollisionGroups::new(OBSTACLE_GROUP, PLAYER_GROUP | ENEMY_GROUP),
+        Obstacle {
+            center: chunk_position + obstacle.position,
+            half_size: obstacle.half_size,
+        },
+        Name::new("Obstacle"),
+    ));
+}
+
+// Returns how many decorations were spawned
+fn spawn_decorations(
+    parent: &mut ChildBuilder,
+    asset_server: &AssetServer,
+    map_data: &MapData,
+    map_level: &MapLevel,
+    spawn_exclusion_zones: &SpawnExclusionZones,
+    chunk: IVec2,
+) -> usize {
+    let chunk_position = map_level.chunk_position(chunk);
+    // same seed for the same chunk, coming back to a chunk shows the same decorations
+    let mut rng = StdRng::seed_from_u64(
+        map_data.seed
+            ^ (chunk.x as u64).wrapping_mul(0x9E37_79B9_7F4A_7C15)
+            ^ (chunk.y as u64).wrapping_mul(0xC2B2_AE3D_27D4_EB4F),
+    );
+    let half_size = map_level.level_size / 2.0;
+    let mut count = 0;
+    for decoration in map_data.decorations.iter() {
+        let texture: Handle<Image> = asset_server.load(&decoration.texture_path);
+        for _ in 0..decoration.count_per_chunk {
+            let Some(rect) = decoration.sprites.choose(&mut rng) else {
+                break;
+            };
+            let position = Vec2::new(
+                rng.gen_range(-half_size.x..half_size.x),
+                rng.gen_range(-half_size.y..half_size.y),
+            );
+            // keep wells and stones clear
+            if spawn_exclusion_zones.contains(chunk_position + position) {
+                continue;
+            }
+            parent.spawn(SpriteBundle {
+                texture: texture.clone(),
+                sprite: Sprite {
+                    rect: Some(*rect),
+                    flip_x: rng.gen_bool(0.5),
+                    ..default()
+                },
+                transform: Transform::from_translation(position.extend(MAP_DECORATION_Z_INDEX)),
+                ..default()
+            });
+            count += 1;
+        }
+    }
+    count
+}
+
+// Tile size depends on the layer, the same tileset can be used with 16px and 32px layers
+fn tileset_layout(
+    texture_atlas_layouts: &mut TextureAtlasLayouts,
+    tileset: &LdtkTilesetDefinition,
+    grid_size: u32,
+) -> (Handle<TextureAtlasLayout>, usize) {
+    let step = grid_size + tileset.spacing;
+    let columns = ((tileset.px_wid - tileset.padding * 2 + tileset.spacing) / step) as usize;
+    let rows = ((tileset.px_hei - tileset.padding * 2 + tileset.spacing) / step) as usize;
+    let layout = texture_atlas_layouts.grid(
+        Vec2::splat(grid_size as f32),
+        columns,
+        rows,
+        Some(Vec2::splat(tileset.spacing as f32)),
+        Some(Vec2::splat(tileset.padding as f32)),
+    );
+    (layout, columns)
+}
+
+// Top left pixel of every cell blocking movement in the layer
+fn obstacle_cells(layer: &LdtkLayerInstance) -> Vec<Vec2> {
+    let mut cells: Vec<IVec2> = match layer.layer_type.as_str() {
+        "IntGrid" => layer.int_grid_cells().collect(),
+        _ => layer
+            .tiles()
+            .map(|tile| IVec2::new(tile.px[0], tile.px[1]))
+            .collect(),
+    };
+    // stacked tiles would give the same obstacle several times
+    cells.sort_by_key(|cell| (cell.x, cell.y));
+    cells.dedup();
+    cells.iter().map(|cell| cell.as_vec2()).collect()
+}
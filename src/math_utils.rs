@@ -1,4 +1,5 @@
 use std::f32::consts::E;
+use crate::components::SpawnExclusionZones;
 use crate::constants::{SCREEN_HEIGHT, SCREEN_WIDTH};
 use bevy::prelude::*;
 use rand::Rng;
@@ -71,7 +72,22 @@ pub fn get_random_position_in_screen() -> Vec2 {
     Vec2::new(x, y)
 }
 
-pub fn get_random_position_outside_screen(camera_position: Vec2) -> Vec2 {
+// Try a few positions outside the screen that are not inside an exclusion zone of the map
+pub fn get_random_position_outside_screen(
+    camera_position: Vec2,
+    spawn_exclusion_zones: &SpawnExclusionZones,
+) -> Vec2 {
+    let mut position = random_position_outside_screen(camera_position);
+    for _ in 0..10 {
+        if !spawn_exclusion_zones.contains(position) {
+            break;
+        }
+        position = random_position_outside_screen(camera_position);
+    }
+    position
+}
+
+fn random_position_outside_screen(camera_position: Vec2) -> Vec2 {
     let mut rng = rand::thread_rng();
     let mut position = Vec2::default();
     let side = rng.gen_range(1..=4);
@@ -102,6 +118,57 @@ pub fn get_random_position_outside_screen(camera_position: Vec2) -> Vec2 {
     camera_position + position
 }
 
+// Closest point outside of the rect for a circle overlapping it, the circle is untouched otherwise
+pub fn push_circle_out_of_rect(
+    center: Vec2,
+    radius: f32,
+    rect_center: Vec2,
+    half_size: Vec2,
+) -> Vec2 {
+    let offset = center - rect_center;
+    let closest = offset.clamp(-half_size, half_size);
+    let distance = offset.distance(closest);
+    if distance >= radius {
+        return center;
+    }
+
+    if distance > 0.0 {
+        // center outside of the rect, push along the closest point
+        return rect_center + closest + (offset - closest) / distance * radius;
+    }
+
+    // center inside of the rect, push out by the shortest side
+    let penetration = half_size - offset.abs();
+    if penetration.x < penetration.y {
+        Vec2::new(
+            rect_center.x + (half_size.x + radius) * offset.x.signum(),
+            center.y,
+        )
+    } else {
+        Vec2::new(
+            center.x,
+            rect_center.y + (half_size.y + radius) * offset.y.signum(),
+        )
+    }
+}
+
+// Flip and rotation to point a sprite drawn facing right toward a direction,
+// flipped instead of upside down when going left
+pub fn sprite_flip_and_rotation(direction: Vec2) -> (bool, Quat) {
+    let is_flip = direction.x < 0.0;
+    let angle = direction.y.atan2(direction.x.abs());
+    match is_flip {
+        true => (true, Quat::from_rotation_z(-angle)),
+        false => (false, Quat::from_rotation_z(angle)),
+    }
+}
+
+// margin grows the screen rect, a negative one shrinks it
+pub fn is_position_outside_screen(camera_position: Vec2, position: Vec2, margin: f32) -> bool {
+    let offset = (position - camera_position).abs();
+    offset.x > SCREEN_WIDTH as f32 / 2.0 + margin || offset.y > SCREEN_HEIGHT as f32 / 2.0 + margin
+}
+
 pub fn find_closest(
     origin: Vec3,
     entities: Query<(Entity, &Transform)>,
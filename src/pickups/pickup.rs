@@ -0,0 +1,183 @@
+use crate::components::*;
+use crate::constants::*;
+use crate::items::item::roll_item;
+use crate::math_utils::is_position_outside_screen;
+use bevy::prelude::*;
+use bevy_rapier2d::prelude::*;
+
+pub struct PickupPlugin;
+
+impl Plugin for PickupPlugin {
+    fn build(&self, app: &mut App) {
+        app.add_systems(
+            Update,
+            (
+                spawn_pickup,
+                pickup_retrieve_by_user,
+                (
+                    apply_floor_chicken,
+                    apply_vacuum,
+                    apply_bomb,
+                    apply_gold_coin,
+                    apply_chest,
+                ),
+            )
+                .chain()
+                .run_if(in_state(GameState::Gameplay)),
+        );
+    }
+}
+
+fn spawn_pickup(
+    mut commands: Commands,
+    asset_server: Res<AssetServer>,
+    mut spawn_pickup: EventReader<OnSpawnPickup>,
+) {
+    for event in spawn_pickup.read() {
+        // chest sprite is twice as big as the other pickups
+        let scale = match event.pickup_type {
+            PickupTypes::Chest => 0.6,
+            _ => 1.0,
+        };
+
+        let pickup = commands
+            .spawn((
+                SpriteBundle {
+                    texture: asset_server.load(event.pickup_type.texture_path()),
+                    transform: Transform {
+                        translation: event.position.truncate().extend(PICKUP_Z_INDEX),
+                        scale: Vec3::splat(scale),
+                        ..default()
+                    },
+                    ..default()
+                },
+                RigidBody::Fixed,
+                Sensor,
+                Collider::ball(6.0),
+                CollisionGroups::new(PICKUP_GROUP, PLAYER_GROUP),
+                ActiveEvents::COLLISION_EVENTS,
+                ActiveCollisionTypes::STATIC_STATIC,
+                CollidingEntities::default(),
+                Pickup {
+                    pickup_type: event.pickup_type,
+                },
+                Name::new("Pickup"),
+            ))
+            .id();
+
+        if event.pickup_type == PickupTypes::Chest {
+            commands
+                .entity(pickup)
+                .insert(OffScreenIndicator(IndicatorTypes::Chest));
+        }
+    }
+}
+
+fn pickup_retrieve_by_user(
+    mut commands: Commands,
+    pickups: Query<(Entity, &Transform, &Pickup, &CollidingEntities)>,
+    player: Query<Entity, With<Player>>,
+    mut pickup_collected: EventWriter<OnPickupCollected>,
+) {
+    let player = player.single();
+    for (pickup_entity, transform, pickup, colliding_entities) in &pickups {
+        if colliding_entities.contains(player) {
+            pickup_collected.send(OnPickupCollected {
+                pickup_type: pickup.pickup_type,
+                position: transform.translation,
+            });
+
+            commands.entity(pickup_entity).despawn_recursive();
+        }
+    }
+}
+
+fn apply_floor_chicken(
+    mut pickup_collected: EventReader<OnPickupCollected>,
+    mut player: Query<(&mut Health, &MaxHealth), With<Player>>,
+) {
+    let (mut health, max_health) = player.single_mut();
+    for event in pickup_collected.read() {
+        if event.pickup_type == PickupTypes::FloorChicken {
+            health.0 = (health.0 + FLOOR_CHICKEN_HEAL).min(max_health.0);
+        }
+    }
+}
+
+// every gem on the ground flies toward the player
+fn apply_vacuum(
+    mut commands: Commands,
+    mut pickup_collected: EventReader<OnPickupCollected>,
+    gems: Query<Entity, (With<Gem>, Without<ColliderDisabled>)>,
+) {
+    for event in pickup_collected.read() {
+        if event.pickup_type == PickupTypes::Vacuum {
+            for gem_entity in &gems {
+                commands.entity(gem_entity).try_insert(GemIsAttracted);
+            }
+        }
+    }
+}
+
+// kills every enemy on screen, bosses are only damaged
+fn apply_bomb(
+    mut pickup_collected: EventReader<OnPickupCollected>,
+    enemies: Query<(Entity, &Transform, &Health, Has<EnemyBossDrop>), KillableEnemy>,
+    player: Query<&Transform, (With<Player>, Without<Enemy>)>,
+    mut enemy_hit: EventWriter<OnEnemyHit>,
+) {
+    let player_position = player.single().translation.truncate();
+    for event in pickup_collected.read() {
+        if event.pickup_type != PickupTypes::Bomb {
+            continue;
+        }
+        // sent as a hit so the kills end up in the statistics like any other weapon
+        for (enemy_entity, transform, health, is_boss) in &enemies {
+            if is_position_outside_screen(player_position, transform.translation.truncate(), 0.0) {
+                continue;
+            }
+            enemy_hit.send(OnEnemyHit {
+                damage: match is_boss {
+                    true => health.0 * 0.5,
+                    false => health.0,
+                },
+                enemy_entity,
+                projectile_position: event.position,
+                impulse: None,
+                projectile_type: ProjectileTypes::Bomb,
+            });
+        }
+    }
+}
+
+fn apply_gold_coin(
+    mut pickup_collected: EventReader<OnPickupCollected>,
+    mut run_statistics: ResMut<RunStatistics>,
+    player_stats: Res<PlayerInGameStats>,
+) {
+    for event in pickup_collected.read() {
+        if event.pickup_type == PickupTypes::GoldCoin {
+            run_statistics.gold_collected += (GOLD_COIN_VALUE * player_stats.greed).round() as u32;
+        }
+    }
+}
+
+// a chest gives a random item from the loot table, like a level up without the choice
+fn apply_chest(
+    mut pickup_collected: EventReader<OnPickupCollected>,
+    loot_table: Res<LootTable>,
+    mut item_pickup: EventWriter<OnItemPickup>,
+) {
+    for event in pickup_collected.read() {
+        if event.pickup_type != PickupTypes::Chest {
+            continue;
+        }
+        // unique items can run out, try a few times before giving up
+        for _ in 0..10 {
+            if let Some((item_key, rarity)) = roll_item(&loot_table) {
+                item_pickup.send(OnItemPickup { item_key, rarity });
+                break;
+            }
+        }
+    }
+}
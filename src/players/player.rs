@@ -1,5 +1,7 @@
 use crate::components::*;
 use crate::constants::*;
+use crate::math_utils::push_circle_out_of_rect;
+use crate::pools::pool::TextureAtlasLayouts;
 use bevy::prelude::*;
 use bevy_rapier2d::prelude::*;
 
@@ -26,8 +28,11 @@ impl Plugin for PlayerPlugin {
                 mul_curse: 0.0,
                 mul_magnet: 0.0,
                 add_extra_life: 1,
+                add_crit_chance: 0.0,
+                add_crit_multiplier: 0.0,
             },
             gold: 0,
+            survived_stages: Vec::new(),
         });
 
         // TODO: add more characters loaded from ron file
@@ -49,6 +54,8 @@ impl Plugin for PlayerPlugin {
                 mul_curse: 0.0,
                 mul_magnet: 0.0,
                 add_extra_life: 0,
+                add_crit_chance: 0.0,
+                add_crit_multiplier: 0.0,
             },
         });
 
@@ -83,6 +90,7 @@ impl Plugin for PlayerPlugin {
                 player_movement,
                 player_received_damage,
                 player_game_over,
+                player_death_animation_finished,
                 compute_experience,
                 player_health_recovery,
             )
@@ -94,18 +102,11 @@ impl Plugin for PlayerPlugin {
 fn setup_player_plugin(
     mut commands: Commands,
     asset_server: Res<AssetServer>,
-    mut texture_atlas_layouts: ResMut<Assets<TextureAtlasLayout>>,
+    mut texture_atlas_layouts: TextureAtlasLayouts,
     player_stats: Res<PlayerInGameStats>,
 ) {
-    let texture = asset_server.load("player.png");
-    let layout = TextureAtlasLayout::from_grid(
-        Vec2::new(16.0, 16.0),
-        4,
-        1,
-        Option::from(Vec2::new(1.0, 1.0)),
-        None,
-    );
-    let texture_atlas_layout = texture_atlas_layouts.add(layout);
+    let sheet = texture_atlas_layouts.aseprite("player-sheet.json");
+    let texture = asset_server.load(&sheet.texture_path);
 
     let player = (
         SpriteBundle {
@@ -114,30 +115,27 @@ fn setup_player_plugin(
             ..default()
         },
         TextureAtlas {
-            layout: texture_atlas_layout,
+            layout: sheet.layout.clone(),
             index: 0,
         },
-        AnimationIndices {
-            first: 0,
-            last: 3,
-            is_repeating: true,
-        },
-        AnimationTimer(Timer::from_seconds(0.1, TimerMode::Repeating)),
+        SpriteAnimation::new(sheet.clone(), "idle"),
+        AnimationController::default(),
         // RigidBody::Dynamic,
         // LockedAxes::ROTATION_LOCKED_Z,
         // Damping {
         //     linear_damping: 100.0,
         //     angular_damping: 1.0,
         // },
-        Collider::ball(4.0),
-        CollisionGroups::new(PLAYER_GROUP, ENEMY_GROUP | GEM_GROUP),
+        Collider::ball(PLAYER_COLLIDER_RADIUS),
+        CollisionGroups::new(PLAYER_GROUP, ENEMY_GROUP | GEM_GROUP | PICKUP_GROUP),
         ActiveEvents::COLLISION_EVENTS,
         ActiveCollisionTypes::default() | ActiveCollisionTypes::STATIC_STATIC,
         Health(player_stats.max_health),
         MaxHealth(player_stats.max_health),
         HealthRecovery(player_stats.recovery),
         Player {
-            facing: Facing::Right,
+            facing: Vec2::X,
+            aim: None,
         },
         Name::new("Player"),
     );
@@ -206,6 +204,12 @@ fn setup_player_in_game_stats(
         (BASE_CURSE * meta_stats.data.mul_curse) + (BASE_CURSE * character_stats.data.mul_curse);
 
     player_stats.extra_life += meta_stats.data.add_extra_life + character_stats.data.add_extra_life;
+
+    player_stats.crit_chance +=
+        meta_stats.data.add_crit_chance + character_stats.data.add_crit_chance;
+
+    player_stats.crit_multiplier +=
+        meta_stats.data.add_crit_multiplier + character_stats.data.add_crit_multiplier;
 }
 
 fn update_player_stats(
@@ -241,16 +245,34 @@ fn update_player_stats(
     println!("greed: {}", player_stats.greed);
     println!("curse: {}", player_stats.curse);
     println!("extra life: {}", player_stats.extra_life);
+    println!("crit chance: {}", player_stats.crit_chance);
+    println!("crit multiplier: {}", player_stats.crit_multiplier);
 }
 
 // public because of the camera, see camera.rs
 pub fn player_movement(
     keyboard_input: Res<ButtonInput<KeyCode>>,
-    mut query: Query<(&mut Transform, &mut Sprite, &mut Player), With<Player>>,
+    mut query: Query<
+        (
+            &mut Transform,
+            &mut Sprite,
+            &mut Player,
+            &mut AnimationController,
+        ),
+        With<Player>,
+    >,
+    obstacles: Query<&Obstacle>,
     player_stats: Res<PlayerInGameStats>,
+    stages: Res<StagesResource>,
+    selected_stage: Res<SelectedStage>,
     time: Res<Time>,
 ) {
-    let (mut avatar_transform, mut avatar_sprite, mut player) = query.single_mut();
+    let (mut avatar_transform, mut avatar_sprite, mut player, mut animation_controller) =
+        query.single_mut();
+    // no more moving while the die clip plays
+    if animation_controller.is_dead() {
+        return;
+    }
     let mut direction = Vec2::new(0., 0.);
 
     if keyboard_input.pressed(KeyCode::ArrowUp) {
@@ -268,44 +290,73 @@ pub fn player_movement(
 
     if direction.x != 0.0 {
         avatar_sprite.flip_x = direction.x < 0.;
-        match avatar_sprite.flip_x {
-            true => player.facing = Facing::Left,
-            false => player.facing = Facing::Right,
-        }
     }
 
     direction = direction.normalize_or_zero();
+    animation_controller.is_moving = direction != Vec2::ZERO;
+    // standing still keeps the last direction for the weapons
+    if animation_controller.is_moving {
+        player.facing = direction;
+    }
 
     avatar_transform.translation.x += direction.x * player_stats.move_speed * time.delta_seconds();
     avatar_transform.translation.y += direction.y * player_stats.move_speed * time.delta_seconds();
+
+    // the player has no rigid body, obstacles are resolved by hand
+    let mut position = avatar_transform.translation.truncate();
+    for obstacle in &obstacles {
+        position = push_circle_out_of_rect(
+            position,
+            PLAYER_COLLIDER_RADIUS,
+            obstacle.center,
+            obstacle.half_size,
+        );
+    }
+    // the camera stops at the stage bounds, the player does too
+    if let Some(bounds) = stages.stages[selected_stage.0].camera_bounds {
+        position = position.clamp(
+            Vec2::new(bounds.min_x, bounds.min_y) + PLAYER_COLLIDER_RADIUS,
+            Vec2::new(bounds.max_x, bounds.max_y) - PLAYER_COLLIDER_RADIUS,
+        );
+    }
+    avatar_transform.translation.x = position.x;
+    avatar_transform.translation.y = position.y;
 }
 
 fn player_game_over(
-    mut health: Query<(&mut Health, &MaxHealth), With<Player>>,
-    mut _game_state: ResMut<NextState<GameState>>,
+    mut health: Query<(&mut Health, &MaxHealth, &mut AnimationController), With<Player>>,
     mut player_stats: ResMut<PlayerInGameStats>,
-    // audio: Res<Audio>,
-    // assets: Res<AssetServer>,
+    mut play_sound: EventWriter<OnPlaySound>,
 ) {
-    let (mut health, max_health) = health.single_mut();
+    let (mut health, max_health, mut animation_controller) = health.single_mut();
 
-    if health.0 <= 0.0 {
+    if health.0 <= 0.0 && !animation_controller.is_dead() {
         if player_stats.extra_life > 0 {
             player_stats.extra_life -= 1;
 
             // TODO: make a state with animation ect instead of just give back health
             //       like a nice revive animation, enemies ect frozen and nice sound
             health.0 = max_health.0 * 0.2;
+            return;
+        }
+        play_sound.send(OnPlaySound {
+            sound: SoundTypes::PlayerDied,
+        });
+        // game over once the die clip is over, see player_death_animation_finished
+        animation_controller.trigger(AnimationStates::Die);
+    }
+}
+
+fn player_death_animation_finished(
+    mut animation_finished: EventReader<OnAnimationFinished>,
+    player: Query<Entity, With<Player>>,
+    mut game_state: ResMut<NextState<GameState>>,
+) {
+    let player = player.single();
+    for event in animation_finished.read() {
+        if event.entity == player && event.clip == AnimationStates::Die.clip() {
+            game_state.set(GameState::GameOver);
         }
-        // audio.play_with_settings(
-        //     assets.load("death.wav"),
-        //     PlaybackSettings {
-        //         repeat: false,
-        //         volume: 0.9,
-        //         speed: 1.0,
-        //     },
-        // );
-        // game_state.set(GameState::GameOver);
     }
 }
 
@@ -344,11 +395,12 @@ fn compute_experience(
 
 fn player_received_damage(
     mut received_damage: EventReader<OnPlayerReceivedDamage>,
-    mut player: Query<&mut Health, With<Player>>,
+    mut player: Query<(&mut Health, &mut AnimationController), With<Player>>,
     player_stats: Res<PlayerInGameStats>,
 ) {
-    let mut player_health = player.single_mut();
+    let (mut player_health, mut animation_controller) = player.single_mut();
     for event in received_damage.read() {
+        animation_controller.trigger(AnimationStates::Hurt);
         // Max is small because we calculate damage over a duration, damage/s
         // The value applied is, therefor, quite small
         player_health.0 -= (event.damage * 1.0 / player_stats.resistance).max(0.000000001);
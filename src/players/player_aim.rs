@@ -0,0 +1,79 @@
+use crate::components::*;
+use crate::constants::AIM_STICK_DEAD_ZONE;
+use bevy::prelude::*;
+use bevy::window::PrimaryWindow;
+
+pub struct PlayerAimPlugin;
+
+impl Plugin for PlayerAimPlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<SelectedAimMode>();
+        app.add_systems(
+            Update,
+            update_player_aim.run_if(in_state(GameState::Gameplay)),
+        );
+    }
+}
+
+// The last device used wins, the cursor only takes over again once it moves
+fn update_player_aim(
+    aim_mode: Res<SelectedAimMode>,
+    mut player: Query<(&Transform, &mut Player)>,
+    window: Query<&Window, With<PrimaryWindow>>,
+    camera: Query<(&Camera, &GlobalTransform)>,
+    gamepads: Res<Gamepads>,
+    axes: Res<Axis<GamepadAxis>>,
+    mut cursor_moved: EventReader<CursorMoved>,
+    mut is_using_mouse: Local<bool>,
+) {
+    let (player_transform, mut player) = player.single_mut();
+
+    if aim_mode.0 == AimMode::Auto {
+        player.aim = None;
+        return;
+    }
+
+    if cursor_moved.read().count() > 0 {
+        *is_using_mouse = true;
+    }
+
+    for gamepad in gamepads.iter() {
+        let stick = Vec2::new(
+            axes.get(GamepadAxis::new(gamepad, GamepadAxisType::RightStickX))
+                .unwrap_or_default(),
+            axes.get(GamepadAxis::new(gamepad, GamepadAxisType::RightStickY))
+                .unwrap_or_default(),
+        );
+        if stick.length() > AIM_STICK_DEAD_ZONE {
+            *is_using_mouse = false;
+            player.aim = Some(stick.normalize());
+            return;
+        }
+    }
+
+    if !*is_using_mouse {
+        return;
+    }
+
+    let Some(cursor_position) = window.get_single().ok().and_then(Window::cursor_position) else {
+        return;
+    };
+    let Ok((camera, camera_transform)) = camera.get_single() else {
+        return;
+    };
+    // the pixel perfect viewport does not start at the corner of the window
+    let viewport_origin = camera
+        .logical_viewport_rect()
+        .map(|rect| rect.min)
+        .unwrap_or_default();
+    let Some(cursor_position) =
+        camera.viewport_to_world_2d(camera_transform, cursor_position - viewport_origin)
+    else {
+        return;
+    };
+
+    let direction = (cursor_position - player_transform.translation.truncate()).normalize_or_zero();
+    if direction != Vec2::ZERO {
+        player.aim = Some(direction);
+    }
+}
@@ -0,0 +1,172 @@
+use crate::animations::aseprite::AsepriteSheet;
+use crate::components::*;
+use bevy::ecs::system::{EntityCommands, SystemParam};
+use bevy::prelude::*;
+use bevy_rapier2d::prelude::*;
+use std::ops::{Deref, DerefMut};
+use std::sync::Arc;
+
+// past this amount of deleted projectiles of one type they are despawned instead
+const MAX_POOLED_PROJECTILES: usize = 256;
+
+// Everything a projectile gets beside its sprite and collider,
+// stripped on release so a reused projectile only has what its new bundle gives
+type ProjectileBehaviours = (
+    (
+        Projectile,
+        ProjectileFixedScale,
+        ProjectileliveForever,
+        ProjectileDamage,
+        ProjectilePierce,
+        ProjectilePositionOnPlayer,
+        ProjectileFollowPlayer,
+        ProjectileTimeBetweenDamage,
+        ProjectileRotateOnSelf,
+        ProjectileOrigin,
+        ProjectileControlPoint,
+        ProjectileSpeed,
+        ProjectileDirection,
+    ),
+    (
+        ProjectileRotateAroundPlayer,
+        ProjectileSpiralAroundPlayer,
+        ProjectileTarget,
+        ProjectileImpulse,
+        ProjectileLifetime,
+        ProjectileDeleteMe,
+        ProjectileSpeedAsDuration,
+        AlreadyHitEnemies,
+    ),
+    (SpriteAnimation, AnimationFinishedAction),
+);
+
+pub struct PoolPlugin;
+
+impl Plugin for PoolPlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<ProjectilePool>();
+        app.init_resource::<GemPool>();
+        app.init_resource::<WorldTextPool>();
+        app.init_resource::<TextureAtlasLayoutCache>();
+    }
+}
+
+// Commands that recycle projectile entities through the ProjectilePool,
+// everything else goes through the regular Commands
+#[derive(SystemParam)]
+pub struct ProjectileCommands<'w, 's> {
+    commands: Commands<'w, 's>,
+    pool: ResMut<'w, ProjectilePool>,
+}
+
+impl<'w, 's> Deref for ProjectileCommands<'w, 's> {
+    type Target = Commands<'w, 's>;
+
+    fn deref(&self) -> &Self::Target {
+        &self.commands
+    }
+}
+
+impl<'w, 's> DerefMut for ProjectileCommands<'w, 's> {
+    fn deref_mut(&mut self) -> &mut Self::Target {
+        &mut self.commands
+    }
+}
+
+impl<'w, 's> ProjectileCommands<'w, 's> {
+    // Reuse a deleted projectile of the same type when there is one, spawn a new one otherwise.
+    // The bundle overwrites the sprite and collider left on the reused entity
+    pub fn spawn_projectile<T: Bundle>(
+        &mut self,
+        projectile_type: ProjectileTypes,
+        bundle: T,
+    ) -> EntityCommands<'_> {
+        if let Some(free) = self.pool.free.get_mut(&projectile_type) {
+            while let Some(entity) = free.pop() {
+                if self.commands.get_entity(entity).is_some() {
+                    let mut entity_commands = self.commands.entity(entity);
+                    entity_commands
+                        .remove::<ColliderDisabled>()
+                        .insert((bundle, ProjectileType(projectile_type)));
+                    return entity_commands;
+                }
+            }
+        }
+        self.commands
+            .spawn((bundle, ProjectileType(projectile_type)))
+    }
+
+    // Hide the projectile and disable its collider, it stays in the pool until reused
+    pub fn release_projectile(&mut self, entity: Entity, projectile_type: ProjectileTypes) {
+        let free = self.pool.free.entry(projectile_type).or_default();
+        if free.len() >= MAX_POOLED_PROJECTILES {
+            self.commands.entity(entity).despawn_recursive();
+            return;
+        }
+        self.commands
+            .entity(entity)
+            .despawn_descendants()
+            .remove::<ProjectileBehaviours>()
+            .insert((ColliderDisabled, Visibility::Hidden));
+        free.push(entity);
+    }
+}
+
+impl GemPool {
+    pub fn release(&mut self, commands: &mut Commands, entity: Entity) {
+        commands
+            .entity(entity)
+            .remove::<(GemIsAttracted, GemOverflow)>()
+            .insert((ColliderDisabled, Visibility::Hidden));
+        self.free.push(entity);
+    }
+}
+
+// Same layout asked twice gives back the same handle instead of a new asset
+#[derive(SystemParam)]
+pub struct TextureAtlasLayouts<'w> {
+    cache: ResMut<'w, TextureAtlasLayoutCache>,
+    layouts: ResMut<'w, Assets<TextureAtlasLayout>>,
+}
+
+impl<'w> TextureAtlasLayouts<'w> {
+    pub fn grid(
+        &mut self,
+        tile_size: Vec2,
+        columns: usize,
+        rows: usize,
+        padding: Option<Vec2>,
+        offset: Option<Vec2>,
+    ) -> Handle<TextureAtlasLayout> {
+        let padding = padding.unwrap_or(Vec2::ZERO);
+        let offset = offset.unwrap_or(Vec2::ZERO);
+        let key: TextureAtlasGridKey = (
+            [tile_size.x.to_bits(), tile_size.y.to_bits()],
+            columns,
+            rows,
+            [padding.x.to_bits(), padding.y.to_bits()],
+            [offset.x.to_bits(), offset.y.to_bits()],
+        );
+
+        if let Some(handle) = self.cache.layouts.get(&key) {
+            return handle.clone();
+        }
+
+        let layout =
+            TextureAtlasLayout::from_grid(tile_size, columns, rows, Some(padding), Some(offset));
+        let handle = self.layouts.add(layout);
+        self.cache.layouts.insert(key, handle.clone());
+        handle
+    }
+
+    // Aseprite json export, read once and shared by every sprite using it
+    pub fn aseprite(&mut self, path: &str) -> Arc<AnimationSheet> {
+        if let Some(sheet) = self.cache.sheets.get(path) {
+            return sheet.clone();
+        }
+
+        let sheet = Arc::new(AsepriteSheet::load(path).to_animation_sheet(path, &mut self.layouts));
+        self.cache.sheets.insert(path.to_string(), sheet.clone());
+        sheet
+    }
+}
@@ -0,0 +1,157 @@
+use crate::components::*;
+use crate::constants::*;
+use crate::math_utils::get_random_position_outside_screen;
+use crate::pools::pool::TextureAtlasLayouts;
+use bevy::prelude::*;
+use bevy::time::common_conditions::on_timer;
+use bevy_rapier2d::prelude::*;
+use rand::distributions::{Distribution, WeightedIndex};
+use std::fs;
+use std::time::Duration;
+
+pub struct PropPlugin;
+
+impl Plugin for PropPlugin {
+    fn build(&self, app: &mut App) {
+        let data = fs::read_to_string("assets/props.ron").unwrap();
+        let props_resource: PropsResource = ron::from_str(&data).unwrap();
+        app.insert_resource(props_resource);
+
+        app.add_systems(
+            Update,
+            spawn_prop
+                .run_if(on_timer(Duration::from_secs_f32(PROP_SPAWN_DELAY)))
+                .run_if(in_state(GameState::Gameplay)),
+        );
+        app.add_systems(
+            Update,
+            (
+                prop_received_damage,
+                prop_destroyed_check,
+                check_prop_too_far,
+            )
+                .chain()
+                .run_if(in_state(GameState::Gameplay)),
+        );
+    }
+}
+
+const PROP_TYPES: [PropTypes; 3] = [
+    PropTypes::Brazier,
+    PropTypes::WoodenCrate,
+    PropTypes::Barrel,
+];
+
+// keep a few props around the player, they always appear just outside the screen
+fn spawn_prop(
+    mut commands: Commands,
+    asset_server: Res<AssetServer>,
+    mut texture_atlas_layouts: TextureAtlasLayouts,
+    props_resource: Res<PropsResource>,
+    props: Query<(), With<Prop>>,
+    player: Query<&Transform, With<Player>>,
+    spawn_exclusion_zones: Res<SpawnExclusionZones>,
+) {
+    if props.iter().count() >= MAX_PROPS {
+        return;
+    }
+
+    let dist = WeightedIndex::new(
+        PROP_TYPES
+            .iter()
+            .map(|prop_type| props_resource.get(*prop_type).spawn_weight),
+    )
+    .unwrap();
+    let prop_type = PROP_TYPES[dist.sample(&mut rand::thread_rng())];
+    let prop_data = props_resource.get(prop_type);
+
+    let player = player.single();
+    let sheet = texture_atlas_layouts.aseprite(&prop_data.animation_path);
+
+    commands.spawn((
+        SpriteSheetBundle {
+            texture: asset_server.load(&sheet.texture_path),
+            atlas: TextureAtlas {
+                layout: sheet.layout.clone(),
+                index: 0,
+            },
+            transform: Transform::from_translation(
+                get_random_position_outside_screen(
+                    player.translation.truncate(),
+                    &spawn_exclusion_zones,
+                )
+                .extend(PROP_Z_INDEX),
+            ),
+            ..default()
+        },
+        SpriteAnimation::new(sheet, "idle"),
+        // projectiles have no rigid body either, fixed colliders need STATIC_STATIC to meet
+        Sensor,
+        Collider::ball(prop_data.collider_radius),
+        CollisionGroups::new(PROP_GROUP, PROJECTILE_GROUP),
+        ActiveCollisionTypes::default() | ActiveCollisionTypes::STATIC_STATIC,
+        Health(prop_data.health),
+        Prop { prop_type },
+        Name::new("Prop"),
+    ));
+}
+
+fn prop_received_damage(
+    mut prop_hit_event: EventReader<OnPropHit>,
+    mut props: Query<&mut Health, With<Prop>>,
+) {
+    for event in prop_hit_event.read() {
+        if let Ok(mut health) = props.get_mut(event.prop_entity) {
+            health.0 -= event.damage;
+        }
+    }
+}
+
+fn prop_destroyed_check(
+    mut commands: Commands,
+    props: Query<(Entity, &Transform, &Health, &Prop)>,
+    props_resource: Res<PropsResource>,
+    mut spawn_pickup: EventWriter<OnSpawnPickup>,
+    mut prop_destroyed: EventWriter<OnPropDestroyed>,
+    player_stats: Res<PlayerInGameStats>,
+) {
+    for (entity, transform, health, prop) in &props {
+        if health.0 > 0.0 {
+            continue;
+        }
+
+        for drop in props_resource.get(prop.prop_type).drops.iter() {
+            if drop.roll(&player_stats) {
+                spawn_pickup.send(OnSpawnPickup {
+                    pickup_type: drop.pickup_type,
+                    position: transform.translation,
+                });
+            }
+        }
+
+        prop_destroyed.send(OnPropDestroyed {
+            prop_type: prop.prop_type,
+            position: transform.translation,
+        });
+
+        commands.entity(entity).despawn_recursive();
+    }
+}
+
+fn check_prop_too_far(
+    mut commands: Commands,
+    player: Query<&Transform, (With<Player>, Without<Prop>)>,
+    props: Query<(Entity, &Transform), With<Prop>>,
+) {
+    let player = player.single();
+    for (prop, transform) in &props {
+        if player
+            .translation
+            .truncate()
+            .distance(transform.translation.truncate())
+            > PROP_DESPAWN_DISTANCE
+        {
+            commands.entity(prop).despawn_recursive();
+        }
+    }
+}
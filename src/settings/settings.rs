@@ -0,0 +1,92 @@
+use crate::cameras::camera::spawn_camera;
+use crate::components::*;
+use crate::constants::{SCREEN_HEIGHT, SCREEN_WIDTH};
+use bevy::prelude::*;
+use bevy::window::{PresentMode, PrimaryWindow, WindowMode};
+use bevy_pixel_camera::PixelZoom;
+use std::fs;
+
+const SAVE_FOLDER: &str = "save";
+const SETTINGS_PATH: &str = "save/settings.ron";
+
+pub struct SettingsPlugin;
+
+impl Plugin for SettingsPlugin {
+    fn build(&self, app: &mut App) {
+        app.insert_resource(load_settings());
+        // also true on the first frame, that's how the settings are applied at startup
+        app.add_systems(
+            Update,
+            (apply_video_settings, apply_audio_settings).run_if(resource_changed::<GameSettings>),
+        );
+        app.add_systems(OnExit(GameState::Settings), save_settings);
+    }
+}
+
+fn load_settings() -> GameSettings {
+    // first launch, nothing saved yet
+    let Ok(data) = fs::read_to_string(SETTINGS_PATH) else {
+        return GameSettings::default();
+    };
+    match ron::from_str::<GameSettings>(&data) {
+        Ok(settings) => settings,
+        Err(error) => {
+            println!("Could not read {}: {}", SETTINGS_PATH, error);
+            GameSettings::default()
+        }
+    }
+}
+
+fn save_settings(settings: Res<GameSettings>) {
+    if let Err(error) = fs::create_dir_all(SAVE_FOLDER) {
+        println!("Could not create {}: {}", SAVE_FOLDER, error);
+        return;
+    }
+    match ron::ser::to_string_pretty(&*settings, ron::ser::PrettyConfig::default()) {
+        Ok(data) => {
+            if let Err(error) = fs::write(SETTINGS_PATH, data) {
+                println!("Could not write {}: {}", SETTINGS_PATH, error);
+            }
+        }
+        Err(error) => println!("Could not serialize the settings: {}", error),
+    }
+}
+
+fn apply_video_settings(
+    mut commands: Commands,
+    settings: Res<GameSettings>,
+    mut windows: Query<&mut Window, With<PrimaryWindow>>,
+    cameras: Query<(Entity, Has<PixelZoom>), With<Camera>>,
+) {
+    let Ok(mut window) = windows.get_single_mut() else {
+        return;
+    };
+    window.mode = match settings.is_fullscreen {
+        true => WindowMode::BorderlessFullscreen,
+        false => WindowMode::Windowed,
+    };
+    window.present_mode = match settings.is_vsync {
+        true => PresentMode::AutoVsync,
+        false => PresentMode::AutoNoVsync,
+    };
+    let width = (SCREEN_WIDTH as u32 * settings.window_scale) as f32;
+    let height = (SCREEN_HEIGHT as u32 * settings.window_scale) as f32;
+    if window.resolution.width() != width || window.resolution.height() != height {
+        window.resolution.set(width, height);
+    }
+
+    // a new camera so bevy_pixel_camera sets its zoom and viewport,
+    // only when switching, the debug zoom is left alone otherwise
+    for (camera, has_pixel_zoom) in &cameras {
+        if settings.is_pixel_perfect != has_pixel_zoom {
+            commands.entity(camera).despawn_recursive();
+            spawn_camera(&mut commands, settings.is_pixel_perfect);
+        }
+    }
+}
+
+fn apply_audio_settings(settings: Res<GameSettings>, mut volumes: ResMut<AudioVolumes>) {
+    volumes.master = settings.master_volume;
+    volumes.music = settings.music_volume;
+    volumes.sfx = settings.sfx_volume;
+}
@@ -0,0 +1,2 @@
+pub mod difficulty;
+pub mod stage;
@@ -0,0 +1,54 @@
+use crate::components::*;
+use bevy::prelude::*;
+
+pub struct DifficultyPlugin;
+
+impl Plugin for DifficultyPlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<SelectedDifficulty>();
+        app.init_resource::<DifficultyScaling>();
+        app.add_systems(
+            Update,
+            update_difficulty_scaling.run_if(in_state(GameState::Gameplay)),
+        );
+    }
+}
+
+fn update_difficulty_scaling(
+    mut difficulty_scaling: ResMut<DifficultyScaling>,
+    selected_difficulty: Res<SelectedDifficulty>,
+    stages: Res<StagesResource>,
+    selected_stage: Res<SelectedStage>,
+    global_timer: Res<WaveManagerGlobalTime>,
+    player_experience: Res<PlayerExperience>,
+    run_statistics: Res<RunStatistics>,
+) {
+    let curve = &stages.stages[selected_stage.0].difficulty_curve;
+    let modifiers = selected_difficulty.0.modifiers();
+    let minutes = global_timer.global_time.elapsed_secs() / 60.0;
+    let levels = player_experience.level.saturating_sub(1) as f32;
+
+    // square root so a strong build still feels strong
+    let dps_factor = match curve.dps_reference {
+        Some(dps_reference) if run_statistics.run_time > 0.0 => {
+            let damage: f32 = run_statistics
+                .weapons
+                .values()
+                .map(|weapon| weapon.damage)
+                .sum();
+            (damage / run_statistics.run_time / dps_reference)
+                .max(1.0)
+                .sqrt()
+        }
+        _ => 1.0,
+    };
+
+    *difficulty_scaling = DifficultyScaling {
+        health: (1.0 + curve.health_per_minute * minutes + curve.health_per_level * levels)
+            * dps_factor
+            * modifiers.health,
+        spawn_amount: (1.0 + curve.spawn_per_minute * minutes) * modifiers.spawn_amount,
+        speed: modifiers.speed,
+        damage: modifiers.damage,
+    };
+}
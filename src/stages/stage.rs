@@ -0,0 +1,14 @@
+use crate::components::*;
+use bevy::prelude::*;
+use std::fs;
+
+pub struct StagePlugin;
+
+impl Plugin for StagePlugin {
+    fn build(&self, app: &mut App) {
+        let data = fs::read_to_string("assets/stages.ron").unwrap();
+        let stages_resource: StagesResource = ron::from_str(&data).unwrap();
+        app.insert_resource(stages_resource);
+        app.init_resource::<SelectedStage>();
+    }
+}
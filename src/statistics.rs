@@ -0,0 +1,2 @@
+pub mod meta_progression;
+pub mod run_statistics;
@@ -0,0 +1,67 @@
+use crate::components::*;
+use crate::statistics::run_statistics::set_run_outcome;
+use bevy::prelude::*;
+use std::fs;
+
+const SAVE_FOLDER: &str = "save";
+const META_PROGRESSION_PATH: &str = "save/meta_progression.ron";
+
+pub struct MetaProgressionPlugin;
+
+impl Plugin for MetaProgressionPlugin {
+    fn build(&self, app: &mut App) {
+        app.add_systems(Startup, load_meta_progression);
+        app.add_systems(
+            OnEnter(GameState::GameOver),
+            save_meta_progression.after(set_run_outcome),
+        );
+    }
+}
+
+fn load_meta_progression(mut meta_stats: ResMut<PlayerMetaStats>) {
+    // first launch, nothing saved yet
+    let Ok(data) = fs::read_to_string(META_PROGRESSION_PATH) else {
+        return;
+    };
+    match ron::from_str::<MetaProgressionSave>(&data) {
+        Ok(save) => {
+            meta_stats.gold = save.gold;
+            meta_stats.survived_stages = save.survived_stages;
+        }
+        Err(error) => warn!("Could not read {}: {}", META_PROGRESSION_PATH, error),
+    }
+}
+
+fn save_meta_progression(
+    mut meta_stats: ResMut<PlayerMetaStats>,
+    run_statistics: Res<RunStatistics>,
+    stages: Res<StagesResource>,
+    selected_stage: Res<SelectedStage>,
+) {
+    meta_stats.gold += run_statistics.gold_collected;
+
+    let stage_name = &stages.stages[selected_stage.0].name;
+    if run_statistics.outcome == RunOutcome::Survived
+        && !meta_stats.survived_stages.contains(stage_name)
+    {
+        meta_stats.survived_stages.push(stage_name.clone());
+    }
+
+    let save = MetaProgressionSave {
+        gold: meta_stats.gold,
+        survived_stages: meta_stats.survived_stages.clone(),
+    };
+
+    if let Err(error) = fs::create_dir_all(SAVE_FOLDER) {
+        error!("Could not create {}: {}", SAVE_FOLDER, error);
+        return;
+    }
+    match ron::ser::to_string_pretty(&save, ron::ser::PrettyConfig::default()) {
+        Ok(data) => {
+            if let Err(error) = fs::write(META_PROGRESSION_PATH, data) {
+                error!("Could not write {}: {}", META_PROGRESSION_PATH, error);
+            }
+        }
+        Err(error) => error!("Could not serialize meta progression: {}", error),
+    }
+}
@@ -0,0 +1,243 @@
+use crate::components::*;
+use bevy::prelude::*;
+use std::fmt::Write as _;
+use std::fs;
+use std::time::{SystemTime, UNIX_EPOCH};
+
+const STATISTICS_FOLDER: &str = "statistics";
+
+pub struct RunStatisticsPlugin;
+
+impl Plugin for RunStatisticsPlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<RunStatistics>();
+        app.add_systems(
+            Update,
+            (
+                track_run_time,
+                track_damage_dealt,
+                track_damage_taken,
+                track_props_destroyed,
+            )
+                .run_if(in_state(GameState::Gameplay)),
+        );
+        app.add_systems(
+            OnEnter(GameState::GameOver),
+            (set_run_outcome, export_run_statistics).chain(),
+        );
+        app.add_systems(
+            Update,
+            export_run_statistics_on_key.run_if(in_state(GameState::Paused)),
+        );
+    }
+}
+
+fn track_run_time(
+    mut run_statistics: ResMut<RunStatistics>,
+    player_weapons: Res<PlayerWeapons>,
+    time: Res<Time>,
+) {
+    let delta = time.delta_seconds();
+    run_statistics.run_time += delta;
+
+    for weapon in player_weapons.weapons.iter() {
+        run_statistics
+            .weapons
+            .entry(*weapon)
+            .or_default()
+            .time_equipped += delta;
+    }
+
+    // projectiles share the time of the weapon firing them, status effects use the run time
+    let run_time = run_statistics.run_time;
+    let RunStatistics {
+        weapons,
+        projectiles,
+        ..
+    } = &mut *run_statistics;
+    for (projectile_type, statistics) in projectiles.iter_mut() {
+        statistics.time_equipped = projectile_type
+            .weapon()
+            .and_then(|weapon| weapons.get(&weapon))
+            .map(|weapon_statistics| weapon_statistics.time_equipped)
+            .unwrap_or(run_time);
+    }
+}
+
+fn track_props_destroyed(
+    mut run_statistics: ResMut<RunStatistics>,
+    mut prop_destroyed: EventReader<OnPropDestroyed>,
+) {
+    run_statistics.props_destroyed += prop_destroyed.read().count() as u32;
+}
+
+fn track_damage_dealt(
+    mut run_statistics: ResMut<RunStatistics>,
+    mut enemy_received_damage: EventReader<OnEnemyReceivedDamage>,
+) {
+    for event in enemy_received_damage.read() {
+        let kills = event.is_killing_blow as u32;
+        run_statistics.enemies_killed += kills;
+
+        let projectile_statistics = run_statistics
+            .projectiles
+            .entry(event.projectile_type)
+            .or_default();
+        projectile_statistics.damage += event.damage;
+        projectile_statistics.hits += 1;
+        projectile_statistics.kills += kills;
+
+        if let Some(weapon) = event.projectile_type.weapon() {
+            let weapon_statistics = run_statistics.weapons.entry(weapon).or_default();
+            weapon_statistics.damage += event.damage;
+            weapon_statistics.hits += 1;
+            weapon_statistics.kills += kills;
+        }
+    }
+}
+
+fn track_damage_taken(
+    mut run_statistics: ResMut<RunStatistics>,
+    mut received_damage: EventReader<OnPlayerReceivedDamage>,
+    player_stats: Res<PlayerInGameStats>,
+) {
+    for event in received_damage.read() {
+        // same formula as player_received_damage
+        *run_statistics
+            .damage_taken
+            .entry(event.enemy_type)
+            .or_default() += event.damage / player_stats.resistance;
+    }
+}
+
+// Dying after the stage duration still counts as survived
+pub fn set_run_outcome(mut run_statistics: ResMut<RunStatistics>) {
+    if run_statistics.outcome == RunOutcome::InProgress {
+        run_statistics.outcome = RunOutcome::Died;
+    }
+}
+
+fn export_run_statistics(run_statistics: Res<RunStatistics>) {
+    save_run_statistics(&run_statistics);
+}
+
+fn export_run_statistics_on_key(
+    run_statistics: Res<RunStatistics>,
+    keyboard_input: Res<ButtonInput<KeyCode>>,
+) {
+    if keyboard_input.just_pressed(KeyCode::F5) {
+        save_run_statistics(&run_statistics);
+    }
+}
+
+fn save_run_statistics(run_statistics: &RunStatistics) {
+    let timestamp = SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map(|duration| duration.as_secs())
+        .unwrap_or(0);
+
+    if let Err(error) = fs::create_dir_all(STATISTICS_FOLDER) {
+        error!("Could not create {}: {}", STATISTICS_FOLDER, error);
+        return;
+    }
+
+    let json_path = format!("{}/run_{}.json", STATISTICS_FOLDER, timestamp);
+    match serde_json::to_string_pretty(run_statistics) {
+        Ok(json) => {
+            if let Err(error) = fs::write(&json_path, json) {
+                error!("Could not write {}: {}", json_path, error);
+            }
+        }
+        Err(error) => error!("Could not serialize run statistics: {}", error),
+    }
+
+    let csv_path = format!("{}/run_{}.csv", STATISTICS_FOLDER, timestamp);
+    if let Err(error) = fs::write(&csv_path, run_statistics_to_csv(run_statistics)) {
+        error!("Could not write {}: {}", csv_path, error);
+    }
+
+    info!("Run statistics saved to {} and {}", json_path, csv_path);
+}
+
+fn sorted_by_damage<K: Copy>(
+    statistics: &std::collections::HashMap<K, DamageStatistics>,
+) -> Vec<(K, &DamageStatistics)> {
+    let mut sorted: Vec<(K, &DamageStatistics)> = statistics
+        .iter()
+        .map(|(key, value)| (*key, value))
+        .collect();
+    sorted.sort_by(|a, b| b.1.damage.total_cmp(&a.1.damage));
+    sorted
+}
+
+pub fn run_statistics_to_csv(run_statistics: &RunStatistics) -> String {
+    let mut csv = String::from("category,name,damage,hits,kills,time_equipped,dps\n");
+
+    for (weapon, statistics) in sorted_by_damage(&run_statistics.weapons) {
+        let _ = writeln!(
+            csv,
+            "weapon,{:?},{:.1},{},{},{:.1},{:.1}",
+            weapon,
+            statistics.damage,
+            statistics.hits,
+            statistics.kills,
+            statistics.time_equipped,
+            statistics.dps()
+        );
+    }
+    for (projectile, statistics) in sorted_by_damage(&run_statistics.projectiles) {
+        let _ = writeln!(
+            csv,
+            "projectile,{:?},{:.1},{},{},{:.1},{:.1}",
+            projectile,
+            statistics.damage,
+            statistics.hits,
+            statistics.kills,
+            statistics.time_equipped,
+            statistics.dps()
+        );
+    }
+    for (enemy_type, damage) in run_statistics.damage_taken.iter() {
+        let _ = writeln!(csv, "damage_taken,{:?},{:.1},,,,", enemy_type, damage);
+    }
+
+    csv
+}
+
+// Text version used by the pause and game over screens
+pub fn run_statistics_to_text(run_statistics: &RunStatistics) -> String {
+    let minutes = (run_statistics.run_time / 60.0) as u32;
+    let seconds = (run_statistics.run_time % 60.0) as u32;
+
+    let mut text = format!(
+        "Time {:02}:{:02}   Kills {}   Gold {}\n\n",
+        minutes, seconds, run_statistics.enemies_killed, run_statistics.gold_collected
+    );
+    let _ = writeln!(
+        text,
+        "{:<16}{:>10}{:>8}{:>8}{:>8}",
+        "WEAPON", "DAMAGE", "HITS", "KILLS", "DPS"
+    );
+    for (weapon, statistics) in sorted_by_damage(&run_statistics.weapons) {
+        let _ = writeln!(
+            text,
+            "{:<16}{:>10.0}{:>8}{:>8}{:>8.1}",
+            format!("{:?}", weapon),
+            statistics.damage,
+            statistics.hits,
+            statistics.kills,
+            statistics.dps()
+        );
+    }
+
+    let mut damage_taken: Vec<(&EnemyTypes, &f32)> = run_statistics.damage_taken.iter().collect();
+    damage_taken.sort_by(|a, b| b.1.total_cmp(a.1));
+    if !damage_taken.is_empty() {
+        let _ = writeln!(text, "\n{:<16}{:>10}", "DAMAGE TAKEN", "");
+        for (enemy_type, damage) in damage_taken {
+            let _ = writeln!(text, "{:<16}{:>10.0}", format!("{:?}", enemy_type), damage);
+        }
+    }
+
+    text
+}
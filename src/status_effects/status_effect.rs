@@ -0,0 +1,176 @@
+use crate::components::*;
+use bevy::prelude::*;
+use bevy_rapier2d::prelude::*;
+
+// delay between two damage over time ticks
+const DAMAGE_OVER_TIME_TICK: f32 = 0.5;
+
+pub struct StatusEffectPlugin;
+
+impl Plugin for StatusEffectPlugin {
+    fn build(&self, app: &mut App) {
+        app.add_systems(
+            Update,
+            (
+                apply_status_effect,
+                status_effect_tick,
+                status_effect_damage_over_time,
+                status_effect_rigid_body,
+                status_effect_visual,
+            )
+                .chain()
+                .run_if(in_state(GameState::Gameplay)),
+        );
+    }
+}
+
+fn apply_status_effect(
+    mut enemies: Query<(&mut StatusEffects, Option<&StatusEffectImmunities>), With<Enemy>>,
+    mut apply_status_effect_event: EventReader<OnApplyStatusEffect>,
+) {
+    for event in apply_status_effect_event.read() {
+        let Ok((mut status_effects, immunities)) = enemies.get_mut(event.enemy_entity) else {
+            continue;
+        };
+
+        if let Some(immunities) = immunities {
+            if immunities.contains(&event.effect_type) {
+                continue;
+            }
+        }
+
+        let stacking = event.effect_type.stacking();
+
+        match status_effects
+            .effects
+            .iter_mut()
+            .find(|effect| effect.effect_type == event.effect_type)
+        {
+            Some(effect) => {
+                effect.lifetime = Timer::from_seconds(event.duration, TimerMode::Once);
+                match stacking {
+                    StatusEffectStacking::Refresh => {
+                        effect.value = effect.value.max(event.value);
+                    }
+                    StatusEffectStacking::StackIntensity { max_stacks } => {
+                        effect.value = event.value;
+                        effect.stacks = (effect.stacks + 1).min(max_stacks);
+                    }
+                }
+            }
+            None => {
+                status_effects.effects.push(StatusEffect {
+                    effect_type: event.effect_type,
+                    value: event.value,
+                    stacks: 1,
+                    lifetime: Timer::from_seconds(event.duration, TimerMode::Once),
+                    tick: Timer::from_seconds(DAMAGE_OVER_TIME_TICK, TimerMode::Repeating),
+                });
+            }
+        }
+    }
+}
+
+fn status_effect_tick(mut enemies: Query<&mut StatusEffects>, time: Res<Time>) {
+    for mut status_effects in &mut enemies {
+        if status_effects.effects.is_empty() {
+            continue;
+        }
+        for effect in status_effects.effects.iter_mut() {
+            effect.lifetime.tick(time.delta());
+            effect.tick.tick(time.delta());
+        }
+        status_effects
+            .effects
+            .retain(|effect| !effect.lifetime.finished());
+    }
+}
+
+fn status_effect_damage_over_time(
+    enemies: Query<(Entity, &Transform, &StatusEffects), With<Enemy>>,
+    mut eneny_hit_event: EventWriter<OnEnemyHit>,
+) {
+    for (enemy_entity, transform, status_effects) in &enemies {
+        for effect in status_effects.effects.iter() {
+            // damage over time is reported with its own projectile type so on hit
+            // effects of the weapon that applied it are not triggered again
+            let projectile_type = match effect.effect_type {
+                StatusEffectTypes::Burn => ProjectileTypes::Burn,
+                StatusEffectTypes::Poison => ProjectileTypes::Poison,
+                _ => continue,
+            };
+            if !effect.tick.just_finished() {
+                continue;
+            }
+            eneny_hit_event.send(OnEnemyHit {
+                damage: effect.value * effect.stacks as f32 * DAMAGE_OVER_TIME_TICK,
+                enemy_entity,
+                projectile_position: transform.translation,
+                impulse: None,
+                projectile_type,
+            });
+        }
+    }
+}
+
+// Stun and freeze lock the enemy in place so it cannot be pushed by other enemies
+fn status_effect_rigid_body(
+    mut commands: Commands,
+    mut enemies: Query<
+        (
+            Entity,
+            &StatusEffects,
+            &RigidBody,
+            Option<&mut SpriteAnimation>,
+        ),
+        With<Enemy>,
+    >,
+) {
+    for (entity, status_effects, rigid_body, animation) in &mut enemies {
+        if status_effects.is_immobilized() {
+            if *rigid_body != RigidBody::Fixed {
+                commands.entity(entity).try_insert(RigidBody::Fixed);
+            }
+        } else if *rigid_body == RigidBody::Fixed {
+            commands.entity(entity).try_insert(RigidBody::Dynamic);
+        }
+
+        // frozen enemies stop animating
+        if let Some(mut animation) = animation {
+            let animation_timer = &mut animation.timer;
+            let is_frozen = status_effects.get(StatusEffectTypes::Freeze).is_some();
+            if is_frozen && !animation_timer.paused() {
+                animation_timer.pause();
+            } else if !is_frozen && animation_timer.paused() {
+                animation_timer.unpause();
+            }
+        }
+    }
+}
+
+// Single place writing the enemy tint, every active effect is blended in so effects
+// do not fight over the sprite color
+fn status_effect_visual(
+    mut enemies: Query<(&StatusEffects, &mut Sprite, Has<EnemyHitFlash>), With<Enemy>>,
+) {
+    for (status_effects, mut sprite, is_hit) in &mut enemies {
+        // above 1 to push the sprite toward white
+        let color = if is_hit {
+            Color::rgb(8.0, 8.0, 8.0)
+        } else if status_effects.effects.is_empty() {
+            Color::WHITE
+        } else {
+            let mut blended = Vec3::ZERO;
+            for effect in status_effects.effects.iter() {
+                let color = effect.effect_type.color();
+                blended += Vec3::new(color.r(), color.g(), color.b());
+            }
+            blended /= status_effects.effects.len() as f32;
+            Color::rgb(blended.x, blended.y, blended.z)
+        };
+
+        if sprite.color != color {
+            sprite.color = color;
+        }
+    }
+}
@@ -1,8 +1,14 @@
 pub mod ui_choose_weapon;
 pub mod ui_enemy;
 pub mod ui_fps;
+pub mod ui_game_over;
 pub mod ui_global_timer;
 pub mod ui_level_up;
 pub mod ui_main_menu;
+pub mod ui_minimap;
+pub mod ui_off_screen_indicator;
+pub mod ui_pause;
 pub mod ui_player;
+pub mod ui_settings;
+pub mod ui_stage_select;
 pub mod ui_update_weapon_up;
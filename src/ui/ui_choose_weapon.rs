@@ -1,5 +1,5 @@
 use crate::components::*;
-use crate::constants::{FONT, FONT_BOLD, SCREEN_WIDTH};
+use crate::constants::{FONT, FONT_BOLD, UI_REFERENCE_ZOOM};
 use bevy::prelude::*;
 use rand::seq::SliceRandom;
 
@@ -36,7 +36,6 @@ fn spawn_choose_weapon_ui(
     mut commands: Commands,
     asset_server: Res<AssetServer>,
     mut next_state: ResMut<NextState<GameState>>,
-    camera: Query<&Camera>,
     player_weapon: Res<PlayerWeapons>,
 ) {
     let weapon_choose_parent = commands
@@ -77,9 +76,6 @@ fn spawn_choose_weapon_ui(
         .entity(weapon_choose_parent)
         .push_children(&[weapon_choose_popup]);
 
-    let camera = camera.single();
-    let view_dimensions = camera.logical_viewport_size().unwrap();
-    let ratio = view_dimensions.x / SCREEN_WIDTH as f32;
 
     let player_weapons = player_weapon.weapons.clone();
 
@@ -107,7 +103,6 @@ fn spawn_choose_weapon_ui(
         let card_item = card_ui_factory(
             &mut commands,
             &asset_server,
-            ratio,
             upgrade,
             &*item_name,
             &*item_description,
@@ -149,7 +144,6 @@ fn update_weapon_button_interaction(
 fn card_ui_factory(
     commands: &mut Commands,
     asset_server: &AssetServer,
-    ratio: f32,
     upgrade: &WeaponsTypes,
     item_name: &str,
     item_description: &str,
@@ -161,8 +155,8 @@ fn card_ui_factory(
             ButtonBundle {
                 style: Style {
                     position_type: PositionType::Relative,
-                    width: Val::Px(80. * ratio),
-                    height: Val::Px(112. * ratio),
+                    width: Val::Px(80. * UI_REFERENCE_ZOOM),
+                    height: Val::Px(112. * UI_REFERENCE_ZOOM),
                     // horizontally center child text
                     justify_content: JustifyContent::Center,
                     // vertically center child text
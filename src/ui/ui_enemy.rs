@@ -1,5 +1,7 @@
 use crate::components::*;
-use crate::constants::{DAMAGE_FONT, DAMAGE_FONT_COLOR, DAMAGE_FONT_SIZE};
+use crate::constants::{
+    DAMAGE_CRITICAL_FONT_SIZE, DAMAGE_FONT, DAMAGE_FONT_COLOR, DAMAGE_FONT_SIZE,
+};
 use bevy::prelude::*;
 
 pub struct UiEnemyPlugin;
@@ -13,99 +15,153 @@ impl Plugin for UiEnemyPlugin {
     }
 }
 
+fn damage_type_color(damage_type: DamageTypes) -> Color {
+    match damage_type {
+        DamageTypes::Physical => DAMAGE_FONT_COLOR,
+        DamageTypes::Fire => Color::rgb(1.0, 0.55, 0.15),
+        DamageTypes::Arcane => Color::rgb(0.75, 0.45, 1.0),
+        DamageTypes::Lightning => Color::rgb(0.937, 0.956, 0.207),
+    }
+}
+
 pub fn enemy_received_damage_ui(
     mut commands: Commands,
-    mut enemies: Query<&Transform, With<Enemy>>,
-    mut eneny_hit_event: EventReader<OnEnemyHit>,
+    mut enemy_received_damage: EventReader<OnEnemyReceivedDamage>,
     asset_server: Res<AssetServer>,
+    mut world_text_pool: ResMut<WorldTextPool>,
+    mut world_texts: Query<(&mut WorldTextUI, &mut Visibility, &mut Style, &Children)>,
+    mut texts: Query<&mut Text>,
+    settings: Res<GameSettings>,
 ) {
-    for event in eneny_hit_event.read() {
-        if let Ok(enemy_transform) = enemies.get_mut(event.enemy_entity) {
-            let parent = commands
-                .spawn((
-                    NodeBundle {
-                        style: Style {
-                            left: Val::Px(-990.),
-                            width: Val::Px(50.),
-                            height: Val::Px(50.),
-                            position_type: PositionType::Absolute,
-                            align_items: AlignItems::FlexStart,
-                            justify_content: JustifyContent::FlexStart,
-                            ..default()
-                        },
-                        z_index: ZIndex::Global(-100),
-                        ..default()
-                    },
-                    WorldTextUI {
-                        lifetime: Timer::from_seconds(2.0, TimerMode::Once),
-                        velocity: Vec2::new(0.0, 10.0),
-                        position: enemy_transform.translation.truncate(),
-                    },
-                    Name::new("Enemy UI"),
-                ))
-                .id();
+    if !settings.show_damage_numbers {
+        enemy_received_damage.clear();
+        return;
+    }
+    for event in enemy_received_damage.read() {
+        let (damage_text, font_size) = if event.is_critical {
+            (
+                format!("{}!", event.damage as i32),
+                DAMAGE_CRITICAL_FONT_SIZE,
+            )
+        } else {
+            (format!("{}", event.damage as i32), DAMAGE_FONT_SIZE)
+        };
 
-            let text = commands
-                .spawn(TextBundle {
-                    text: Text {
-                        sections: vec![TextSection::new(
-                            &format!("{:?}", event.damage as i32),
-                            TextStyle {
-                                font: asset_server.load(DAMAGE_FONT),
-                                font_size: DAMAGE_FONT_SIZE,
-                                color: DAMAGE_FONT_COLOR,
-                            },
-                        )],
-                        ..default()
-                    },
-                    z_index: ZIndex::Local(1),
-                    ..default()
-                })
-                .id();
+        // reuse a finished text instead of building a new node tree
+        if let Some(parent) = world_text_pool.free.pop() {
+            if let Ok((mut world_ui, mut visibility, mut style, children)) =
+                world_texts.get_mut(parent)
+            {
+                world_ui.lifetime.reset();
+                world_ui.position = event.position.truncate();
+                style.left = Val::Px(-990.);
+                *visibility = Visibility::Inherited;
 
-            let text_shadow = commands
-                .spawn(TextBundle {
-                    text: Text {
-                        sections: vec![TextSection::new(
-                            &format!("{:?}", event.damage as i32),
-                            TextStyle {
-                                font: asset_server.load(DAMAGE_FONT),
-                                font_size: DAMAGE_FONT_SIZE,
-                                color: Color::BLACK,
-                            },
-                        )],
-                        ..default()
-                    },
+                // first child is the text, second one its shadow
+                for (index, child) in children.iter().enumerate() {
+                    if let Ok(mut text) = texts.get_mut(*child) {
+                        text.sections[0].value.clone_from(&damage_text);
+                        text.sections[0].style.font_size = font_size;
+                        if index == 0 {
+                            text.sections[0].style.color = damage_type_color(event.damage_type);
+                        }
+                    }
+                }
+                continue;
+            }
+        }
+
+        let parent = commands
+            .spawn((
+                NodeBundle {
                     style: Style {
+                        left: Val::Px(-990.),
+                        width: Val::Px(50.),
+                        height: Val::Px(50.),
                         position_type: PositionType::Absolute,
-                        top: Val::Px(2.0),
-                        left: Val::Px(2.0),
+                        align_items: AlignItems::FlexStart,
+                        justify_content: JustifyContent::FlexStart,
                         ..default()
                     },
-                    z_index: ZIndex::Local(-1),
+                    z_index: ZIndex::Global(-100),
                     ..default()
-                })
-                .id();
+                },
+                WorldTextUI {
+                    lifetime: Timer::from_seconds(2.0, TimerMode::Once),
+                    velocity: Vec2::new(0.0, 10.0),
+                    position: event.position.truncate(),
+                },
+                Name::new("Enemy UI"),
+            ))
+            .id();
 
-            commands.entity(parent).push_children(&[text, text_shadow]);
-        }
+        let text = commands
+            .spawn(TextBundle {
+                text: Text {
+                    sections: vec![TextSection::new(
+                        damage_text.clone(),
+                        TextStyle {
+                            font: asset_server.load(DAMAGE_FONT),
+                            font_size,
+                            color: damage_type_color(event.damage_type),
+                        },
+                    )],
+                    ..default()
+                },
+                z_index: ZIndex::Local(1),
+                ..default()
+            })
+            .id();
+
+        let text_shadow = commands
+            .spawn(TextBundle {
+                text: Text {
+                    sections: vec![TextSection::new(
+                        damage_text,
+                        TextStyle {
+                            font: asset_server.load(DAMAGE_FONT),
+                            font_size,
+                            color: Color::BLACK,
+                        },
+                    )],
+                    ..default()
+                },
+                style: Style {
+                    position_type: PositionType::Absolute,
+                    top: Val::Px(2.0),
+                    left: Val::Px(2.0),
+                    ..default()
+                },
+                z_index: ZIndex::Local(-1),
+                ..default()
+            })
+            .id();
+
+        commands.entity(parent).push_children(&[text, text_shadow]);
     }
 }
 
 fn update_enemy_received_damage_ui(
-    mut commands: Commands,
-    mut text: Query<(Entity, &mut Style, &mut WorldTextUI)>,
+    mut text: Query<(Entity, &mut Style, &mut WorldTextUI, &mut Visibility)>,
+    mut world_text_pool: ResMut<WorldTextPool>,
     main_camera: Query<(&Camera, &GlobalTransform)>,
     // render_camera: Query<&Camera>,
     time: Res<Time>,
+    ui_scale: Res<UiScale>,
 ) {
     let (camera, transform) = main_camera.single();
     // let final_camera = render_camera.single();
 
-    for (entity, mut style, mut world_ui) in &mut text {
+    for (entity, mut style, mut world_ui, mut visibility) in &mut text {
+        // already waiting in the pool
+        if world_ui.lifetime.finished() {
+            continue;
+        }
         world_ui.lifetime.tick(time.delta());
         if world_ui.lifetime.just_finished() {
-            commands.entity(entity).despawn_recursive();
+            *visibility = Visibility::Hidden;
+            world_text_pool.free.push(entity);
+            continue;
         }
 
         world_ui.position = world_ui.position + world_ui.velocity * time.delta_seconds();
@@ -114,8 +170,9 @@ fn update_enemy_received_damage_ui(
             // let mut coords = coords / Vec2::new(SCREEN_WIDTH as f32, SCREEN_HEIGHT as f32)
             //     * camera.logical_viewport_size().unwrap();
             // coords.y = camera.logical_viewport_size().unwrap().y - coords.y;
-            style.left = Val::Px(coords.x);
-            style.top = Val::Px(coords.y);
+            // the ui lives in the camera viewport, only the ui scale is left to undo
+            style.left = Val::Px(coords.x / ui_scale.0);
+            style.top = Val::Px(coords.y / ui_scale.0);
         }
     }
 }
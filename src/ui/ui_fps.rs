@@ -1,3 +1,4 @@
+use crate::components::GameSettings;
 use bevy::diagnostic::DiagnosticsStore;
 use bevy::diagnostic::FrameTimeDiagnosticsPlugin;
 use bevy::prelude::*;
@@ -7,7 +8,14 @@ pub struct UiFPSPlugin;
 impl Plugin for UiFPSPlugin {
     fn build(&self, app: &mut App) {
         app.add_systems(Startup, setup_fps_counter);
-        app.add_systems(Update, (fps_text_update_system, fps_counter_showhide));
+        app.add_systems(
+            Update,
+            (
+                fps_text_update_system,
+                fps_counter_showhide,
+                fps_counter_visibility.run_if(resource_changed::<GameSettings>),
+            ),
+        );
     }
 }
 
@@ -127,15 +135,20 @@ fn fps_text_update_system(
 }
 
 /// Toggle the FPS counter when pressing F12
-fn fps_counter_showhide(
+fn fps_counter_showhide(mut settings: ResMut<GameSettings>, kbd: Res<ButtonInput<KeyCode>>) {
+    if kbd.just_pressed(KeyCode::F12) {
+        settings.show_fps = !settings.show_fps;
+    }
+}
+
+fn fps_counter_visibility(
     mut q: Query<&mut Visibility, With<FpsRoot>>,
-    kbd: Res<ButtonInput<KeyCode>>,
+    settings: Res<GameSettings>,
 ) {
-    if kbd.just_pressed(KeyCode::F12) {
-        let mut vis = q.single_mut();
-        *vis = match *vis {
-            Visibility::Hidden => Visibility::Visible,
-            _ => Visibility::Hidden,
+    for mut vis in &mut q {
+        *vis = match settings.show_fps {
+            true => Visibility::Visible,
+            false => Visibility::Hidden,
         };
     }
 }
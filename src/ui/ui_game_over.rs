@@ -0,0 +1,159 @@
+use crate::components::*;
+use crate::constants::{FONT, FONT_BOLD};
+use crate::statistics::run_statistics::run_statistics_to_text;
+use bevy::app::AppExit;
+use bevy::prelude::*;
+
+pub struct UiGameOverPlugin;
+
+impl Plugin for UiGameOverPlugin {
+    fn build(&self, app: &mut App) {
+        app.add_systems(OnEnter(GameState::GameOver), spawn_game_over_ui);
+        app.add_systems(OnExit(GameState::GameOver), despawn_game_over_ui);
+        app.add_systems(
+            Update,
+            game_over_button_interaction.run_if(in_state(GameState::GameOver)),
+        );
+    }
+}
+
+const HOVERED_BUTTON: Color = Color::rgb(0.80, 0.80, 0.80);
+const NORMAL_BUTTON: Color = Color::rgb(1., 1., 1.);
+const POPUP_BG_COLOR: Color = Color::rgba(0.0, 0.0, 0.0, 0.95);
+const SURVIVED_COLOR: Color = Color::rgb(0.4, 0.9, 0.4);
+
+fn despawn_game_over_ui(mut commands: Commands, ui: Query<Entity, With<GameOverUI>>) {
+    for ui in &ui {
+        commands.entity(ui).despawn_recursive();
+    }
+}
+
+fn spawn_game_over_ui(
+    mut commands: Commands,
+    asset_server: Res<AssetServer>,
+    run_statistics: Res<RunStatistics>,
+) {
+    let game_over_parent = commands
+        .spawn((
+            NodeBundle {
+                style: Style {
+                    width: Val::Percent(100.0),
+                    height: Val::Percent(100.0),
+                    position_type: PositionType::Absolute,
+                    align_items: AlignItems::Center,
+                    justify_content: JustifyContent::Center,
+                    ..default()
+                },
+                ..default()
+            },
+            GameOverUI,
+            Name::new("UI game over"),
+        ))
+        .id();
+
+    let game_over_popup = commands
+        .spawn(NodeBundle {
+            style: Style {
+                width: Val::Percent(80.0),
+                height: Val::Percent(80.0),
+                position_type: PositionType::Relative,
+                flex_direction: FlexDirection::Column,
+                align_items: AlignItems::Center,
+                justify_content: JustifyContent::SpaceAround,
+                padding: UiRect::all(Val::Px(20.0)),
+                ..default()
+            },
+            background_color: POPUP_BG_COLOR.into(),
+            ..default()
+        })
+        .id();
+
+    let (title_text, title_color) = match run_statistics.outcome {
+        RunOutcome::Survived => ("SURVIVED", SURVIVED_COLOR),
+        _ => ("GAME OVER", Color::WHITE),
+    };
+
+    let title = commands
+        .spawn(TextBundle::from_section(
+            title_text,
+            TextStyle {
+                font: asset_server.load(FONT_BOLD),
+                font_size: 48.0,
+                color: title_color,
+            },
+        ))
+        .id();
+
+    let statistics = commands
+        .spawn(TextBundle::from_section(
+            run_statistics_to_text(&run_statistics),
+            TextStyle {
+                font: asset_server.load(FONT),
+                font_size: 20.0,
+                color: Color::WHITE,
+            },
+        ))
+        .id();
+
+    let quit_button = commands
+        .spawn((
+            ButtonBundle {
+                style: Style {
+                    width: Val::Px(200.),
+                    height: Val::Px(70.0),
+                    justify_content: JustifyContent::Center,
+                    align_items: AlignItems::Center,
+                    ..default()
+                },
+                background_color: NORMAL_BUTTON.into(),
+                ..default()
+            },
+            GameOverQuitButton,
+        ))
+        .id();
+
+    let quit_button_label = commands
+        .spawn(TextBundle::from_section(
+            "Quit",
+            TextStyle {
+                font: asset_server.load(FONT_BOLD),
+                font_size: 36.0,
+                color: Color::BLACK,
+            },
+        ))
+        .id();
+
+    commands
+        .entity(quit_button)
+        .push_children(&[quit_button_label]);
+
+    commands
+        .entity(game_over_popup)
+        .push_children(&[title, statistics, quit_button]);
+
+    commands
+        .entity(game_over_parent)
+        .push_children(&[game_over_popup]);
+}
+
+fn game_over_button_interaction(
+    mut app_exit: EventWriter<AppExit>,
+    mut quit_button: Query<(Ref<Interaction>, &mut BackgroundColor), With<GameOverQuitButton>>,
+) {
+    for (interaction, mut color) in &mut quit_button {
+        if !interaction.is_changed() {
+            continue;
+        }
+        match *interaction {
+            Interaction::Pressed => {
+                app_exit.send(AppExit);
+            }
+            Interaction::Hovered => {
+                *color = HOVERED_BUTTON.into();
+            }
+            Interaction::None => {
+                *color = NORMAL_BUTTON.into();
+            }
+        }
+    }
+}
@@ -1,9 +1,7 @@
 use crate::components::*;
-use crate::constants::{FONT, FONT_BOLD, SCREEN_WIDTH};
+use crate::constants::{FONT, FONT_BOLD, UI_REFERENCE_ZOOM};
+use crate::items::item::roll_item;
 use bevy::prelude::*;
-use rand::distributions::{Distribution, WeightedIndex};
-use rand::seq::SliceRandom;
-use rand::Rng;
 
 pub struct UiLevelUpPlugin;
 
@@ -32,7 +30,6 @@ fn spawn_level_up_ui(
     mut commands: Commands,
     asset_server: Res<AssetServer>,
     mut texture_atlas_layouts: ResMut<Assets<TextureAtlasLayout>>,
-    camera: Query<&Camera>,
     loot_table: Res<LootTable>,
     items_resource: Res<ItemsResource>,
 ) {
@@ -74,27 +71,10 @@ fn spawn_level_up_ui(
         .entity(level_up_parent)
         .push_children(&[level_up_popup]);
 
-    let camera = camera.single();
-    let view_dimensions = camera.logical_viewport_size().unwrap();
-    let ratio = view_dimensions.x / SCREEN_WIDTH as f32;
-
-    let dist = WeightedIndex::new(loot_table.weighted_rarity.iter().map(|item| item.1)).unwrap();
 
     let mut item_to_offer = 5;
     while item_to_offer > 0 {
-        let mut rarity = loot_table.weighted_rarity[dist.sample(&mut rand::thread_rng())].0;
-
-        // has a small chance to be a cursed item
-        if rand::thread_rng().gen_range(0.0..100.0) < 1.0 {
-            rarity = loot_table.weighted_rarity[5].0;
-        }
-
-        let Some(item_key) = loot_table
-            .item_by_rarity
-            .get(&rarity)
-            .unwrap()
-            .choose(&mut rand::thread_rng())
-        else {
+        let Some((item_key, rarity)) = roll_item(&loot_table) else {
             // unique rarity has a list of items that can be removed over time
             // To avoid the function to panic, we simply continue the loop and try again to pick
             // another rarity/item.
@@ -132,9 +112,8 @@ fn spawn_level_up_ui(
             &mut commands,
             &asset_server,
             &mut texture_atlas_layouts,
-            ratio,
             &rarity,
-            &*item_key,
+            &item_key,
             &*item_name,
             &*item_description,
             texture_atlas_index,
@@ -176,7 +155,6 @@ fn card_ui_factory(
     commands: &mut Commands,
     asset_server: &AssetServer,
     texture_atlas_layouts: &mut Assets<TextureAtlasLayout>,
-    ratio: f32,
     rarity: &Rarity,
     item_key: &str,
     item_name: &str,
@@ -212,8 +190,8 @@ fn card_ui_factory(
             ButtonBundle {
                 style: Style {
                     position_type: PositionType::Relative,
-                    width: Val::Px(80. * ratio),
-                    height: Val::Px(112. * ratio),
+                    width: Val::Px(80. * UI_REFERENCE_ZOOM),
+                    height: Val::Px(112. * UI_REFERENCE_ZOOM),
                     // horizontally center child text
                     justify_content: JustifyContent::Center,
                     // vertically center child text
@@ -236,8 +214,8 @@ fn card_ui_factory(
             ButtonBundle {
                 style: Style {
                     position_type: PositionType::Relative,
-                    width: Val::Px(74. * ratio),
-                    height: Val::Px(61. * ratio),
+                    width: Val::Px(74. * UI_REFERENCE_ZOOM),
+                    height: Val::Px(61. * UI_REFERENCE_ZOOM),
                     align_self: AlignSelf::Start,
                     margin: UiRect::top(Val::Px(11.0)),
                     ..default()
@@ -11,13 +11,20 @@ impl Plugin for UiMainMenuPlugin {
         app.add_systems(OnExit(GameState::MainMenu), despawn_main_menu_ui);
         app.add_systems(
             Update,
-            update_main_menu_button_interaction.run_if(in_state(GameState::MainMenu)),
+            (
+                update_main_menu_button_interaction,
+                update_main_menu_settings_button_interaction,
+                update_main_menu_difficulty_button_interaction,
+                update_main_menu_aim_mode_button_interaction,
+            )
+                .run_if(in_state(GameState::MainMenu)),
         );
     }
 }
 
 const HOVERED_BUTTON: Color = Color::rgb(0.0, 0.80, 0.80);
 const NORMAL_BUTTON: Color = Color::rgb(0., 1., 1.);
+const SELECTED_BUTTON: Color = Color::rgb(1.0, 0.85, 0.2);
 const POPUP_BG_COLOR: Color = Color::rgba(0.0, 0.0, 0.0, 0.95);
 
 fn despawn_main_menu_ui(mut commands: Commands, ui: Query<Entity, With<MainMenuUI>>) {
@@ -26,7 +33,12 @@ fn despawn_main_menu_ui(mut commands: Commands, ui: Query<Entity, With<MainMenuU
     }
 }
 
-fn spawn_main_menu_ui(mut commands: Commands, asset_server: Res<AssetServer>) {
+fn spawn_main_menu_ui(
+    mut commands: Commands,
+    asset_server: Res<AssetServer>,
+    selected_difficulty: Res<SelectedDifficulty>,
+    selected_aim_mode: Res<SelectedAimMode>,
+) {
     let main_menu_parent = commands
         .spawn((
             NodeBundle {
@@ -52,7 +64,7 @@ fn spawn_main_menu_ui(mut commands: Commands, asset_server: Res<AssetServer>) {
                 height: Val::Percent(80.0),
                 position_type: PositionType::Relative,
                 align_items: AlignItems::Center,
-                flex_direction: FlexDirection::Row,
+                flex_direction: FlexDirection::Column,
                 justify_content: JustifyContent::SpaceAround,
                 ..default()
             },
@@ -99,9 +111,149 @@ fn spawn_main_menu_ui(mut commands: Commands, asset_server: Res<AssetServer>) {
         .entity(play_button)
         .push_children(&[play_button_label]);
 
+    let settings_button = commands
+        .spawn((
+            ButtonBundle {
+                style: Style {
+                    width: Val::Px(200.),
+                    height: Val::Px(50.0),
+                    justify_content: JustifyContent::Center,
+                    align_items: AlignItems::Center,
+                    ..default()
+                },
+                background_color: NORMAL_BUTTON.into(),
+                z_index: ZIndex::Global(10),
+                ..default()
+            },
+            MainMenuSettingsButton,
+        ))
+        .id();
+
+    let settings_button_label = commands
+        .spawn(TextBundle::from_section(
+            "Settings",
+            TextStyle {
+                font: asset_server.load(FONT_BOLD),
+                font_size: 24.0,
+                color: Color::BLACK,
+            },
+        ))
+        .id();
+
     commands
-        .entity(main_menu_popup)
-        .push_children(&[play_button]);
+        .entity(settings_button)
+        .push_children(&[settings_button_label]);
+
+    let difficulty_row = commands
+        .spawn(NodeBundle {
+            style: Style {
+                flex_direction: FlexDirection::Row,
+                column_gap: Val::Px(16.0),
+                ..default()
+            },
+            ..default()
+        })
+        .id();
+
+    for difficulty in Difficulty::ALL {
+        let difficulty_button = commands
+            .spawn((
+                ButtonBundle {
+                    style: Style {
+                        width: Val::Px(120.),
+                        height: Val::Px(50.0),
+                        justify_content: JustifyContent::Center,
+                        align_items: AlignItems::Center,
+                        ..default()
+                    },
+                    background_color: match difficulty == selected_difficulty.0 {
+                        true => SELECTED_BUTTON.into(),
+                        false => NORMAL_BUTTON.into(),
+                    },
+                    z_index: ZIndex::Global(10),
+                    ..default()
+                },
+                MainMenuDifficultyButton { difficulty },
+            ))
+            .id();
+
+        let difficulty_button_label = commands
+            .spawn(TextBundle::from_section(
+                format!("{:?}", difficulty),
+                TextStyle {
+                    font: asset_server.load(FONT_BOLD),
+                    font_size: 24.0,
+                    color: Color::BLACK,
+                },
+            ))
+            .id();
+
+        commands
+            .entity(difficulty_button)
+            .push_children(&[difficulty_button_label]);
+        commands
+            .entity(difficulty_row)
+            .push_children(&[difficulty_button]);
+    }
+
+    let aim_mode_row = commands
+        .spawn(NodeBundle {
+            style: Style {
+                flex_direction: FlexDirection::Row,
+                column_gap: Val::Px(16.0),
+                ..default()
+            },
+            ..default()
+        })
+        .id();
+
+    for aim_mode in AimMode::ALL {
+        let aim_mode_button = commands
+            .spawn((
+                ButtonBundle {
+                    style: Style {
+                        width: Val::Px(200.),
+                        height: Val::Px(50.0),
+                        justify_content: JustifyContent::Center,
+                        align_items: AlignItems::Center,
+                        ..default()
+                    },
+                    background_color: match aim_mode == selected_aim_mode.0 {
+                        true => SELECTED_BUTTON.into(),
+                        false => NORMAL_BUTTON.into(),
+                    },
+                    z_index: ZIndex::Global(10),
+                    ..default()
+                },
+                MainMenuAimModeButton { aim_mode },
+            ))
+            .id();
+
+        let aim_mode_button_label = commands
+            .spawn(TextBundle::from_section(
+                format!("Aim: {:?}", aim_mode),
+                TextStyle {
+                    font: asset_server.load(FONT_BOLD),
+                    font_size: 24.0,
+                    color: Color::BLACK,
+                },
+            ))
+            .id();
+
+        commands
+            .entity(aim_mode_button)
+            .push_children(&[aim_mode_button_label]);
+        commands
+            .entity(aim_mode_row)
+            .push_children(&[aim_mode_button]);
+    }
+
+    commands.entity(main_menu_popup).push_children(&[
+        play_button,
+        settings_button,
+        difficulty_row,
+        aim_mode_row,
+    ]);
 
     commands
         .entity(main_menu_parent)
@@ -118,7 +270,7 @@ fn update_main_menu_button_interaction(
     for (interaction, mut image, upgrade) in &mut interaction_query {
         match *interaction {
             Interaction::Pressed => {
-                next_state.set(GameState::Gameplay);
+                next_state.set(GameState::StageSelect);
             }
             Interaction::Hovered => {
                 *image = HOVERED_BUTTON.into();
@@ -129,3 +281,78 @@ fn update_main_menu_button_interaction(
         }
     }
 }
+
+fn update_main_menu_settings_button_interaction(
+    mut next_state: ResMut<NextState<GameState>>,
+    mut interaction_query: Query<
+        (&Interaction, &mut BackgroundColor, &MainMenuSettingsButton),
+        Changed<Interaction>,
+    >,
+) {
+    for (interaction, mut image, _) in &mut interaction_query {
+        match *interaction {
+            Interaction::Pressed => {
+                next_state.set(GameState::Settings);
+            }
+            Interaction::Hovered => {
+                *image = HOVERED_BUTTON.into();
+            }
+            Interaction::None => {
+                *image = NORMAL_BUTTON.into();
+            }
+        }
+    }
+}
+
+// the selected difficulty stays highlighted
+fn update_main_menu_difficulty_button_interaction(
+    mut selected_difficulty: ResMut<SelectedDifficulty>,
+    changed_buttons: Query<(&Interaction, &MainMenuDifficultyButton), Changed<Interaction>>,
+    mut buttons: Query<(
+        &Interaction,
+        &mut BackgroundColor,
+        &MainMenuDifficultyButton,
+    )>,
+) {
+    for (interaction, button) in &changed_buttons {
+        if *interaction == Interaction::Pressed {
+            selected_difficulty.0 = button.difficulty;
+        }
+    }
+
+    // a new selection repaints the other buttons too
+    if changed_buttons.is_empty() && !selected_difficulty.is_changed() {
+        return;
+    }
+    for (interaction, mut color, button) in &mut buttons {
+        *color = match (*interaction, button.difficulty == selected_difficulty.0) {
+            (_, true) => SELECTED_BUTTON.into(),
+            (Interaction::Hovered, false) => HOVERED_BUTTON.into(),
+            _ => NORMAL_BUTTON.into(),
+        };
+    }
+}
+
+// same as the difficulty, manual aim uses the mouse or the right stick
+fn update_main_menu_aim_mode_button_interaction(
+    mut selected_aim_mode: ResMut<SelectedAimMode>,
+    changed_buttons: Query<(&Interaction, &MainMenuAimModeButton), Changed<Interaction>>,
+    mut buttons: Query<(&Interaction, &mut BackgroundColor, &MainMenuAimModeButton)>,
+) {
+    for (interaction, button) in &changed_buttons {
+        if *interaction == Interaction::Pressed {
+            selected_aim_mode.0 = button.aim_mode;
+        }
+    }
+
+    if changed_buttons.is_empty() && !selected_aim_mode.is_changed() {
+        return;
+    }
+    for (interaction, mut color, button) in &mut buttons {
+        *color = match (*interaction, button.aim_mode == selected_aim_mode.0) {
+            (_, true) => SELECTED_BUTTON.into(),
+            (Interaction::Hovered, false) => HOVERED_BUTTON.into(),
+            _ => NORMAL_BUTTON.into(),
+        };
+    }
+}
@@ -0,0 +1,220 @@
+use crate::components::*;
+use crate::constants::{
+    MINIMAP_ENEMY_HEAT_MAX, MINIMAP_TEXTURE_SIZE, MINIMAP_UI_SIZE, MINIMAP_UPDATE_INTERVAL,
+    MINIMAP_WORLD_RADIUS,
+};
+use bevy::prelude::*;
+use bevy::render::render_asset::RenderAssetUsages;
+use bevy::render::render_resource::{Extent3d, TextureDimension, TextureFormat};
+use bevy_rapier2d::prelude::ColliderDisabled;
+
+const BACKGROUND_COLOR: [u8; 4] = [20, 20, 30, 160];
+const OBSTACLE_COLOR: [u8; 4] = [90, 90, 105, 220];
+const GEM_COLOR: [u8; 4] = [80, 220, 120, 255];
+const PICKUP_COLOR: [u8; 4] = [255, 200, 50, 255];
+const BOSS_GEM_COLOR: [u8; 4] = [80, 200, 255, 255];
+const BOSS_COLOR: [u8; 4] = [255, 40, 40, 255];
+const PLAYER_COLOR: [u8; 4] = [255, 255, 255, 255];
+
+pub struct UiMinimapPlugin;
+
+impl Plugin for UiMinimapPlugin {
+    fn build(&self, app: &mut App) {
+        app.add_systems(Startup, setup_minimap_ui);
+        app.add_systems(
+            Update,
+            (
+                minimap_showhide,
+                minimap_visibility,
+                draw_minimap.run_if(in_state(GameState::Gameplay)),
+            ),
+        );
+    }
+}
+
+fn setup_minimap_ui(mut commands: Commands, mut images: ResMut<Assets<Image>>) {
+    let image = images.add(Image::new_fill(
+        Extent3d {
+            width: MINIMAP_TEXTURE_SIZE,
+            height: MINIMAP_TEXTURE_SIZE,
+            depth_or_array_layers: 1,
+        },
+        TextureDimension::D2,
+        &BACKGROUND_COLOR,
+        TextureFormat::Rgba8UnormSrgb,
+        RenderAssetUsages::default(),
+    ));
+
+    commands.spawn((
+        ImageBundle {
+            style: Style {
+                position_type: PositionType::Absolute,
+                right: Val::Percent(1.),
+                bottom: Val::Percent(1.),
+                width: Val::Px(MINIMAP_UI_SIZE),
+                height: Val::Px(MINIMAP_UI_SIZE),
+                ..default()
+            },
+            image: UiImage::new(image.clone()),
+            visibility: Visibility::Hidden,
+            ..default()
+        },
+        MinimapUI,
+        Name::new("Minimap UI"),
+    ));
+
+    commands.insert_resource(Minimap {
+        image,
+        timer: Timer::from_seconds(MINIMAP_UPDATE_INTERVAL, TimerMode::Repeating),
+    });
+}
+
+/// Toggle the minimap when pressing M
+fn minimap_showhide(mut settings: ResMut<GameSettings>, kbd: Res<ButtonInput<KeyCode>>) {
+    if kbd.just_pressed(KeyCode::KeyM) {
+        settings.show_minimap = !settings.show_minimap;
+    }
+}
+
+fn minimap_visibility(
+    mut minimap_ui: Query<&mut Visibility, With<MinimapUI>>,
+    settings: Res<GameSettings>,
+    state: Res<State<GameState>>,
+) {
+    let is_in_menu = matches!(
+        state.get(),
+        GameState::MainMenu | GameState::StageSelect | GameState::Settings
+    );
+    for mut visibility in &mut minimap_ui {
+        *visibility = match settings.show_minimap && !is_in_menu {
+            true => Visibility::Visible,
+            false => Visibility::Hidden,
+        };
+    }
+}
+
+// Redrawn a few times per second, hundreds of enemies every frame add up
+fn draw_minimap(
+    mut minimap: ResMut<Minimap>,
+    mut images: ResMut<Assets<Image>>,
+    time: Res<Time>,
+    settings: Res<GameSettings>,
+    player: Query<&Transform, With<Player>>,
+    enemies: Query<(&Transform, Has<EnemyBossDrop>), With<Enemy>>,
+    gems: Query<&Transform, (With<Gem>, Without<ColliderDisabled>)>,
+    pickups: Query<&Transform, With<Pickup>>,
+    boss_gems: Query<&Transform, With<GemBoss>>,
+    obstacles: Query<&Obstacle>,
+) {
+    if !settings.show_minimap || !minimap.timer.tick(time.delta()).just_finished() {
+        return;
+    }
+    let Ok(player) = player.get_single() else {
+        return;
+    };
+    let Some(image) = images.get_mut(&minimap.image) else {
+        return;
+    };
+
+    let center = player.translation.truncate();
+    let size = MINIMAP_TEXTURE_SIZE as usize;
+    let data = &mut image.data;
+
+    for pixel in data.chunks_exact_mut(4) {
+        pixel.copy_from_slice(&BACKGROUND_COLOR);
+    }
+
+    for obstacle in &obstacles {
+        let min = to_minimap(obstacle.center - obstacle.half_size, center);
+        let max = to_minimap(obstacle.center + obstacle.half_size, center);
+        // y is flipped, so the world max is the top of the texture
+        let (min_x, max_x) = (min.x.max(0), max.x.min(size as i32 - 1));
+        let (min_y, max_y) = (max.y.max(0), min.y.min(size as i32 - 1));
+        for y in min_y..=max_y {
+            for x in min_x..=max_x {
+                set_pixel(data, IVec2::new(x, y), OBSTACLE_COLOR);
+            }
+        }
+    }
+
+    // enemy density, the more enemies on a pixel the hotter it gets
+    let mut heat = vec![0u16; size * size];
+    let mut bosses = Vec::new();
+    for (transform, is_boss) in &enemies {
+        let pixel = to_minimap(transform.translation.truncate(), center);
+        if is_boss {
+            bosses.push(pixel);
+        }
+        if let Some(index) = pixel_index(pixel) {
+            heat[index] = heat[index].saturating_add(1);
+        }
+    }
+    for (index, count) in heat.into_iter().enumerate() {
+        if count == 0 {
+            continue;
+        }
+        let heat = (count as f32 / MINIMAP_ENEMY_HEAT_MAX).min(1.0);
+        data[index * 4..index * 4 + 4].copy_from_slice(&[
+            (140.0 + 115.0 * heat) as u8,
+            (40.0 + 160.0 * heat * heat) as u8,
+            40,
+            255,
+        ]);
+    }
+
+    for transform in &gems {
+        set_pixel(
+            data,
+            to_minimap(transform.translation.truncate(), center),
+            GEM_COLOR,
+        );
+    }
+
+    for transform in &pickups {
+        let pixel = to_minimap(transform.translation.truncate(), center);
+        set_pixel(data, pixel, PICKUP_COLOR);
+    }
+
+    for transform in &boss_gems {
+        let pixel = to_minimap(transform.translation.truncate(), center);
+        set_square(data, pixel, 1, BOSS_GEM_COLOR);
+    }
+
+    for pixel in bosses {
+        set_square(data, pixel, 1, BOSS_COLOR);
+    }
+
+    set_square(data, to_minimap(center, center), 1, PLAYER_COLOR);
+}
+
+// Texture pixel of a world position, may be outside of the texture
+fn to_minimap(position: Vec2, center: Vec2) -> IVec2 {
+    let half_size = MINIMAP_TEXTURE_SIZE as f32 / 2.0;
+    let pixel = (position - center) * half_size / MINIMAP_WORLD_RADIUS + half_size;
+    IVec2::new(
+        pixel.x.floor() as i32,
+        MINIMAP_TEXTURE_SIZE as i32 - 1 - pixel.y.floor() as i32,
+    )
+}
+
+fn pixel_index(pixel: IVec2) -> Option<usize> {
+    let size = MINIMAP_TEXTURE_SIZE as i32;
+    if pixel.x < 0 || pixel.y < 0 || pixel.x >= size || pixel.y >= size {
+        return None;
+    }
+    Some((pixel.y * size + pixel.x) as usize)
+}
+
+fn set_pixel(data: &mut [u8], pixel: IVec2, color: [u8; 4]) {
+    if let Some(index) = pixel_index(pixel) {
+        data[index * 4..index * 4 + 4].copy_from_slice(&color);
+    }
+}
+
+fn set_square(data: &mut [u8], pixel: IVec2, radius: i32, color: [u8; 4]) {
+    for y in -radius..=radius {
+        for x in -radius..=radius {
+            set_pixel(data, pixel + IVec2::new(x, y), color);
+        }
+    }
+}
@@ -0,0 +1,154 @@
+use crate::components::*;
+use crate::constants::{
+    FONT, OFF_SCREEN_INDICATOR_ARROW_SIZE, OFF_SCREEN_INDICATOR_MARGIN, OFF_SCREEN_INDICATOR_SIZE,
+    PIXELS_PER_METER,
+};
+use bevy::prelude::*;
+
+pub struct UiOffScreenIndicatorPlugin;
+
+impl Plugin for UiOffScreenIndicatorPlugin {
+    fn build(&self, app: &mut App) {
+        app.add_systems(
+            Update,
+            (spawn_off_screen_indicators, update_off_screen_indicators).chain(),
+        );
+    }
+}
+
+fn spawn_off_screen_indicators(
+    mut commands: Commands,
+    asset_server: Res<AssetServer>,
+    targets: Query<(Entity, &OffScreenIndicator), Added<OffScreenIndicator>>,
+) {
+    for (target, indicator) in &targets {
+        commands
+            .spawn((
+                NodeBundle {
+                    style: Style {
+                        position_type: PositionType::Absolute,
+                        width: Val::Px(OFF_SCREEN_INDICATOR_SIZE),
+                        height: Val::Px(OFF_SCREEN_INDICATOR_SIZE),
+                        flex_direction: FlexDirection::Column,
+                        align_items: AlignItems::Center,
+                        justify_content: JustifyContent::Center,
+                        ..default()
+                    },
+                    visibility: Visibility::Hidden,
+                    ..default()
+                },
+                OffScreenIndicatorUI { target },
+                Name::new("Off Screen Indicator"),
+            ))
+            .with_children(|parent| {
+                // points from the middle of the indicator toward the target
+                parent.spawn((
+                    ImageBundle {
+                        style: Style {
+                            position_type: PositionType::Absolute,
+                            width: Val::Px(OFF_SCREEN_INDICATOR_ARROW_SIZE),
+                            height: Val::Px(OFF_SCREEN_INDICATOR_ARROW_SIZE),
+                            ..default()
+                        },
+                        image: UiImage::new(asset_server.load("indicator_arrow.png")),
+                        background_color: indicator.0.color().into(),
+                        ..default()
+                    },
+                    OffScreenIndicatorArrowUI,
+                ));
+                parent.spawn(ImageBundle {
+                    style: Style {
+                        height: Val::Px(12.0),
+                        ..default()
+                    },
+                    image: UiImage::new(asset_server.load(indicator.0.icon_path())),
+                    ..default()
+                });
+                parent.spawn((
+                    TextBundle::from_section(
+                        "",
+                        TextStyle {
+                            font: asset_server.load(FONT),
+                            font_size: 10.0,
+                            color: indicator.0.color(),
+                        },
+                    ),
+                    OffScreenIndicatorDistanceUI,
+                ));
+            });
+    }
+}
+
+// The ui lives in the camera viewport, so viewport coordinates only need the ui scale
+fn update_off_screen_indicators(
+    mut commands: Commands,
+    mut indicators: Query<
+        (Entity, &OffScreenIndicatorUI, &mut Style, &mut Visibility),
+        Without<OffScreenIndicatorArrowUI>,
+    >,
+    mut arrows: Query<(&Parent, &mut Style, &mut Transform), With<OffScreenIndicatorArrowUI>>,
+    mut distances: Query<(&Parent, &mut Text), With<OffScreenIndicatorDistanceUI>>,
+    targets: Query<&GlobalTransform>,
+    player: Query<&GlobalTransform, With<Player>>,
+    camera: Query<(&Camera, &GlobalTransform), With<CameraController>>,
+    ui_scale: Res<UiScale>,
+) {
+    let Ok((camera, camera_transform)) = camera.get_single() else {
+        return;
+    };
+    let Some(viewport_size) = camera.logical_viewport_size() else {
+        return;
+    };
+    let Ok(player) = player.get_single() else {
+        return;
+    };
+
+    let center = viewport_size / 2.0;
+    let edge = (center - OFF_SCREEN_INDICATOR_MARGIN * ui_scale.0).max(Vec2::ZERO);
+
+    for (entity, indicator, mut style, mut visibility) in &mut indicators {
+        // the boss died or the chest was opened
+        let Ok(target) = targets.get(indicator.target) else {
+            commands.entity(entity).despawn_recursive();
+            continue;
+        };
+
+        let offset = camera
+            .world_to_viewport(camera_transform, target.translation())
+            .map(|position| position - center);
+        let offset = match offset {
+            Some(offset) if offset.x.abs() > center.x || offset.y.abs() > center.y => offset,
+            _ => {
+                *visibility = Visibility::Hidden;
+                continue;
+            }
+        };
+
+        // push the indicator back along the line from the center until it hits the margin
+        let scale = (edge.x / offset.x.abs()).min(edge.y / offset.y.abs());
+        let position = (center + offset * scale) / ui_scale.0;
+        style.left = Val::Px(position.x - OFF_SCREEN_INDICATOR_SIZE / 2.0);
+        style.top = Val::Px(position.y - OFF_SCREEN_INDICATOR_SIZE / 2.0);
+        *visibility = Visibility::Inherited;
+
+        // viewport y goes down, like the ui
+        let direction = offset.normalize();
+        for (parent, mut arrow_style, mut arrow_transform) in &mut arrows {
+            if parent.get() != entity {
+                continue;
+            }
+            let arrow_position = (direction + 1.0) * OFF_SCREEN_INDICATOR_SIZE / 2.0
+                - OFF_SCREEN_INDICATOR_ARROW_SIZE / 2.0;
+            arrow_style.left = Val::Px(arrow_position.x);
+            arrow_style.top = Val::Px(arrow_position.y);
+            arrow_transform.rotation = Quat::from_rotation_z(direction.y.atan2(direction.x));
+        }
+
+        let distance = target.translation().distance(player.translation()) / PIXELS_PER_METER;
+        for (parent, mut text) in &mut distances {
+            if parent.get() == entity {
+                text.sections[0].value = format!("{}m", distance as i32);
+            }
+        }
+    }
+}
@@ -0,0 +1,121 @@
+use crate::components::*;
+use crate::constants::{FONT, FONT_BOLD};
+use crate::statistics::run_statistics::run_statistics_to_text;
+use bevy::prelude::*;
+
+pub struct UiPausePlugin;
+
+impl Plugin for UiPausePlugin {
+    fn build(&self, app: &mut App) {
+        app.add_systems(OnEnter(GameState::Paused), spawn_pause_ui);
+        app.add_systems(OnExit(GameState::Paused), despawn_pause_ui);
+        app.add_systems(
+            Update,
+            toggle_pause.run_if(in_state(GameState::Gameplay).or_else(in_state(GameState::Paused))),
+        );
+    }
+}
+
+const POPUP_BG_COLOR: Color = Color::rgba(0.0, 0.0, 0.0, 0.85);
+
+fn toggle_pause(
+    keyboard_input: Res<ButtonInput<KeyCode>>,
+    state: Res<State<GameState>>,
+    mut next_state: ResMut<NextState<GameState>>,
+) {
+    if !keyboard_input.just_pressed(KeyCode::Tab) {
+        return;
+    }
+    match state.get() {
+        GameState::Gameplay => next_state.set(GameState::Paused),
+        GameState::Paused => next_state.set(GameState::Gameplay),
+        _ => {}
+    }
+}
+
+fn despawn_pause_ui(mut commands: Commands, ui: Query<Entity, With<PauseUI>>) {
+    for ui in &ui {
+        commands.entity(ui).despawn_recursive();
+    }
+}
+
+fn spawn_pause_ui(
+    mut commands: Commands,
+    asset_server: Res<AssetServer>,
+    run_statistics: Res<RunStatistics>,
+) {
+    let pause_parent = commands
+        .spawn((
+            NodeBundle {
+                style: Style {
+                    width: Val::Percent(100.0),
+                    height: Val::Percent(100.0),
+                    position_type: PositionType::Absolute,
+                    align_items: AlignItems::Center,
+                    justify_content: JustifyContent::Center,
+                    ..default()
+                },
+                ..default()
+            },
+            PauseUI,
+            Name::new("UI pause"),
+        ))
+        .id();
+
+    let pause_popup = commands
+        .spawn(NodeBundle {
+            style: Style {
+                width: Val::Percent(80.0),
+                height: Val::Percent(80.0),
+                position_type: PositionType::Relative,
+                flex_direction: FlexDirection::Column,
+                align_items: AlignItems::Center,
+                justify_content: JustifyContent::FlexStart,
+                padding: UiRect::all(Val::Px(20.0)),
+                row_gap: Val::Px(20.0),
+                ..default()
+            },
+            background_color: POPUP_BG_COLOR.into(),
+            ..default()
+        })
+        .id();
+
+    let title = commands
+        .spawn(TextBundle::from_section(
+            "PAUSE",
+            TextStyle {
+                font: asset_server.load(FONT_BOLD),
+                font_size: 48.0,
+                color: Color::WHITE,
+            },
+        ))
+        .id();
+
+    let statistics = commands
+        .spawn(TextBundle::from_section(
+            run_statistics_to_text(&run_statistics),
+            TextStyle {
+                font: asset_server.load(FONT),
+                font_size: 20.0,
+                color: Color::WHITE,
+            },
+        ))
+        .id();
+
+    let help = commands
+        .spawn(TextBundle::from_section(
+            "TAB resume - F5 export statistics",
+            TextStyle {
+                font: asset_server.load(FONT),
+                font_size: 16.0,
+                color: Color::GRAY,
+            },
+        ))
+        .id();
+
+    commands
+        .entity(pause_popup)
+        .push_children(&[title, statistics, help]);
+
+    commands.entity(pause_parent).push_children(&[pause_popup]);
+}
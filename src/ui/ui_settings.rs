@@ -0,0 +1,300 @@
+use crate::components::*;
+use crate::constants::{FONT, FONT_BOLD, SCREEN_HEIGHT, SCREEN_WIDTH};
+use bevy::prelude::*;
+
+pub struct UiSettingsPlugin;
+
+impl Plugin for UiSettingsPlugin {
+    fn build(&self, app: &mut App) {
+        app.add_systems(OnEnter(GameState::Settings), spawn_settings_ui);
+        app.add_systems(OnExit(GameState::Settings), despawn_settings_ui);
+        app.add_systems(
+            Update,
+            (
+                update_settings_button_interaction,
+                update_settings_back_button_interaction,
+                update_settings_labels.run_if(resource_changed::<GameSettings>),
+            )
+                .run_if(in_state(GameState::Settings)),
+        );
+    }
+}
+
+const HOVERED_BUTTON: Color = Color::rgb(0.0, 0.80, 0.80);
+const NORMAL_BUTTON: Color = Color::rgb(0., 1., 1.);
+const POPUP_BG_COLOR: Color = Color::rgba(0.0, 0.0, 0.0, 0.95);
+
+const UI_SCALE_STEPS: [f32; 4] = [0.75, 1.0, 1.25, 1.5];
+const VOLUME_STEPS: [f32; 6] = [0.0, 0.2, 0.4, 0.6, 0.8, 1.0];
+const SCREEN_SHAKE_STEPS: [f32; 3] = [0.0, 0.5, 1.0];
+const MAX_WINDOW_SCALE: u32 = 4;
+
+fn on_off(value: bool) -> &'static str {
+    match value {
+        true => "On",
+        false => "Off",
+    }
+}
+
+fn percent(value: f32) -> String {
+    format!("{}%", (value * 100.0).round() as i32)
+}
+
+// next value of the list, back to the first one after the last
+fn next_step(value: f32, steps: &[f32]) -> f32 {
+    steps
+        .iter()
+        .copied()
+        .find(|step| *step > value + 0.001)
+        .unwrap_or(steps[0])
+}
+
+fn settings_label(option: SettingsOption, settings: &GameSettings) -> String {
+    match option {
+        SettingsOption::WindowScale => format!(
+            "Window size: {}x{}",
+            SCREEN_WIDTH as u32 * settings.window_scale,
+            SCREEN_HEIGHT as u32 * settings.window_scale
+        ),
+        SettingsOption::Fullscreen => format!("Fullscreen: {}", on_off(settings.is_fullscreen)),
+        SettingsOption::Vsync => format!("VSync: {}", on_off(settings.is_vsync)),
+        SettingsOption::PixelPerfect => {
+            format!("Pixel perfect: {}", on_off(settings.is_pixel_perfect))
+        }
+        SettingsOption::UiScale => format!("UI scale: {}", percent(settings.ui_scale)),
+        SettingsOption::MasterVolume => {
+            format!("Master volume: {}", percent(settings.master_volume))
+        }
+        SettingsOption::MusicVolume => format!("Music volume: {}", percent(settings.music_volume)),
+        SettingsOption::SfxVolume => format!("SFX volume: {}", percent(settings.sfx_volume)),
+        SettingsOption::DamageNumbers => {
+            format!("Damage numbers: {}", on_off(settings.show_damage_numbers))
+        }
+        SettingsOption::ScreenShake => format!("Screen shake: {}", percent(settings.screen_shake)),
+        SettingsOption::FpsCounter => format!("FPS counter: {}", on_off(settings.show_fps)),
+        SettingsOption::Minimap => format!("Minimap: {}", on_off(settings.show_minimap)),
+    }
+}
+
+fn cycle_setting(option: SettingsOption, settings: &mut GameSettings) {
+    match option {
+        SettingsOption::WindowScale => {
+            settings.window_scale = settings.window_scale % MAX_WINDOW_SCALE + 1
+        }
+        SettingsOption::Fullscreen => settings.is_fullscreen = !settings.is_fullscreen,
+        SettingsOption::Vsync => settings.is_vsync = !settings.is_vsync,
+        SettingsOption::PixelPerfect => settings.is_pixel_perfect = !settings.is_pixel_perfect,
+        SettingsOption::UiScale => {
+            settings.ui_scale = next_step(settings.ui_scale, &UI_SCALE_STEPS)
+        }
+        SettingsOption::MasterVolume => {
+            settings.master_volume = next_step(settings.master_volume, &VOLUME_STEPS)
+        }
+        SettingsOption::MusicVolume => {
+            settings.music_volume = next_step(settings.music_volume, &VOLUME_STEPS)
+        }
+        SettingsOption::SfxVolume => {
+            settings.sfx_volume = next_step(settings.sfx_volume, &VOLUME_STEPS)
+        }
+        SettingsOption::DamageNumbers => {
+            settings.show_damage_numbers = !settings.show_damage_numbers
+        }
+        SettingsOption::ScreenShake => {
+            settings.screen_shake = next_step(settings.screen_shake, &SCREEN_SHAKE_STEPS)
+        }
+        SettingsOption::FpsCounter => settings.show_fps = !settings.show_fps,
+        SettingsOption::Minimap => settings.show_minimap = !settings.show_minimap,
+    }
+}
+
+fn despawn_settings_ui(mut commands: Commands, ui: Query<Entity, With<SettingsUI>>) {
+    for ui in &ui {
+        commands.entity(ui).despawn_recursive();
+    }
+}
+
+fn spawn_settings_ui(
+    mut commands: Commands,
+    asset_server: Res<AssetServer>,
+    settings: Res<GameSettings>,
+) {
+    let settings_parent = commands
+        .spawn((
+            NodeBundle {
+                style: Style {
+                    width: Val::Percent(100.0),
+                    height: Val::Percent(100.0),
+                    position_type: PositionType::Absolute,
+                    align_items: AlignItems::Center,
+                    justify_content: JustifyContent::Center,
+                    ..default()
+                },
+                ..default()
+            },
+            SettingsUI,
+            Name::new("UI settings"),
+        ))
+        .id();
+
+    let settings_popup = commands
+        .spawn(NodeBundle {
+            style: Style {
+                width: Val::Percent(80.0),
+                height: Val::Percent(90.0),
+                position_type: PositionType::Relative,
+                flex_direction: FlexDirection::Column,
+                align_items: AlignItems::Center,
+                justify_content: JustifyContent::Center,
+                row_gap: Val::Px(6.0),
+                ..default()
+            },
+            background_color: POPUP_BG_COLOR.into(),
+            ..default()
+        })
+        .id();
+
+    let title = commands
+        .spawn(TextBundle::from_section(
+            "SETTINGS",
+            TextStyle {
+                font: asset_server.load(FONT_BOLD),
+                font_size: 40.0,
+                color: Color::WHITE,
+            },
+        ))
+        .id();
+    commands.entity(settings_popup).push_children(&[title]);
+
+    let button_style = Style {
+        width: Val::Px(360.),
+        height: Val::Px(34.0),
+        justify_content: JustifyContent::Center,
+        align_items: AlignItems::Center,
+        ..default()
+    };
+
+    for option in SettingsOption::ALL {
+        let settings_button = commands
+            .spawn((
+                ButtonBundle {
+                    style: button_style.clone(),
+                    background_color: NORMAL_BUTTON.into(),
+                    z_index: ZIndex::Global(10),
+                    ..default()
+                },
+                SettingsButton { option },
+            ))
+            .id();
+
+        let settings_button_label = commands
+            .spawn(TextBundle::from_section(
+                settings_label(option, &settings),
+                TextStyle {
+                    font: asset_server.load(FONT),
+                    font_size: 20.0,
+                    color: Color::BLACK,
+                },
+            ))
+            .id();
+
+        commands
+            .entity(settings_button)
+            .push_children(&[settings_button_label]);
+        commands
+            .entity(settings_popup)
+            .push_children(&[settings_button]);
+    }
+
+    let back_button = commands
+        .spawn((
+            ButtonBundle {
+                style: button_style,
+                background_color: NORMAL_BUTTON.into(),
+                z_index: ZIndex::Global(10),
+                ..default()
+            },
+            SettingsBackButton,
+        ))
+        .id();
+
+    let back_button_label = commands
+        .spawn(TextBundle::from_section(
+            "Back",
+            TextStyle {
+                font: asset_server.load(FONT_BOLD),
+                font_size: 24.0,
+                color: Color::BLACK,
+            },
+        ))
+        .id();
+
+    commands
+        .entity(back_button)
+        .push_children(&[back_button_label]);
+    commands
+        .entity(settings_popup)
+        .push_children(&[back_button]);
+
+    commands
+        .entity(settings_parent)
+        .push_children(&[settings_popup]);
+}
+
+// every click moves the option to its next value, applied right away
+fn update_settings_button_interaction(
+    mut settings: ResMut<GameSettings>,
+    mut interaction_query: Query<
+        (&Interaction, &mut BackgroundColor, &SettingsButton),
+        Changed<Interaction>,
+    >,
+) {
+    for (interaction, mut image, settings_button) in &mut interaction_query {
+        match *interaction {
+            Interaction::Pressed => {
+                cycle_setting(settings_button.option, &mut settings);
+            }
+            Interaction::Hovered => {
+                *image = HOVERED_BUTTON.into();
+            }
+            Interaction::None => {
+                *image = NORMAL_BUTTON.into();
+            }
+        }
+    }
+}
+
+fn update_settings_back_button_interaction(
+    mut next_state: ResMut<NextState<GameState>>,
+    mut interaction_query: Query<
+        (&Interaction, &mut BackgroundColor, &SettingsBackButton),
+        Changed<Interaction>,
+    >,
+) {
+    for (interaction, mut image, _) in &mut interaction_query {
+        match *interaction {
+            Interaction::Pressed => {
+                next_state.set(GameState::MainMenu);
+            }
+            Interaction::Hovered => {
+                *image = HOVERED_BUTTON.into();
+            }
+            Interaction::None => {
+                *image = NORMAL_BUTTON.into();
+            }
+        }
+    }
+}
+
+fn update_settings_labels(
+    settings: Res<GameSettings>,
+    settings_buttons: Query<(&SettingsButton, &Children)>,
+    mut texts: Query<&mut Text>,
+) {
+    for (settings_button, children) in &settings_buttons {
+        for child in children.iter() {
+            if let Ok(mut text) = texts.get_mut(*child) {
+                text.sections[0].value = settings_label(settings_button.option, &settings);
+            }
+        }
+    }
+}
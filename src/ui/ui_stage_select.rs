@@ -0,0 +1,188 @@
+use crate::components::*;
+use crate::constants::{FONT, FONT_BOLD};
+use bevy::prelude::*;
+
+pub struct UiStageSelectPlugin;
+
+impl Plugin for UiStageSelectPlugin {
+    fn build(&self, app: &mut App) {
+        app.add_systems(OnEnter(GameState::StageSelect), spawn_stage_select_ui);
+        app.add_systems(OnExit(GameState::StageSelect), despawn_stage_select_ui);
+        app.add_systems(
+            Update,
+            update_stage_select_button_interaction.run_if(in_state(GameState::StageSelect)),
+        );
+    }
+}
+
+const HOVERED_BUTTON: Color = Color::rgb(0.0, 0.80, 0.80);
+const NORMAL_BUTTON: Color = Color::rgb(0., 1., 1.);
+const LOCKED_STAGE: Color = Color::rgb(0.4, 0.4, 0.4);
+const POPUP_BG_COLOR: Color = Color::rgba(0.0, 0.0, 0.0, 0.95);
+
+fn despawn_stage_select_ui(mut commands: Commands, ui: Query<Entity, With<StageSelectUI>>) {
+    for ui in &ui {
+        commands.entity(ui).despawn_recursive();
+    }
+}
+
+fn spawn_stage_select_ui(
+    mut commands: Commands,
+    asset_server: Res<AssetServer>,
+    stages: Res<StagesResource>,
+    meta_stats: Res<PlayerMetaStats>,
+) {
+    let stage_select_parent = commands
+        .spawn((
+            NodeBundle {
+                style: Style {
+                    width: Val::Percent(100.0),
+                    height: Val::Percent(100.0),
+                    position_type: PositionType::Absolute,
+                    align_items: AlignItems::Center,
+                    justify_content: JustifyContent::Center,
+                    ..default()
+                },
+                ..default()
+            },
+            StageSelectUI,
+            Name::new("UI stage select"),
+        ))
+        .id();
+
+    let stage_select_popup = commands
+        .spawn(NodeBundle {
+            style: Style {
+                width: Val::Percent(80.0),
+                height: Val::Percent(80.0),
+                position_type: PositionType::Relative,
+                align_items: AlignItems::Center,
+                flex_direction: FlexDirection::Row,
+                justify_content: JustifyContent::SpaceAround,
+                ..default()
+            },
+            background_color: POPUP_BG_COLOR.into(),
+            ..default()
+        })
+        .id();
+
+    let stage_style = Style {
+        position_type: PositionType::Relative,
+        width: Val::Px(320.),
+        padding: UiRect::all(Val::Px(16.0)),
+        flex_direction: FlexDirection::Column,
+        justify_content: JustifyContent::Center,
+        align_items: AlignItems::Center,
+        row_gap: Val::Px(8.0),
+        ..default()
+    };
+
+    for (stage_index, stage) in stages.stages.iter().enumerate() {
+        // locked stages are shown but can't be clicked
+        let locked_by = stage
+            .unlocked_by
+            .as_ref()
+            .filter(|required| !meta_stats.survived_stages.contains(required));
+
+        let stage_button = match locked_by {
+            Some(_) => commands
+                .spawn(NodeBundle {
+                    style: stage_style.clone(),
+                    background_color: LOCKED_STAGE.into(),
+                    z_index: ZIndex::Global(10),
+                    ..default()
+                })
+                .id(),
+            None => commands
+                .spawn((
+                    ButtonBundle {
+                        style: stage_style.clone(),
+                        border_color: BorderColor(Color::BLACK),
+                        background_color: NORMAL_BUTTON.into(),
+                        z_index: ZIndex::Global(10),
+                        ..default()
+                    },
+                    StageSelectButton { stage_index },
+                ))
+                .id(),
+        };
+
+        let minutes = (stage.duration / 60.0) as u32;
+        let seconds = (stage.duration % 60.0) as u32;
+        let enemies = stage
+            .enemy_roster
+            .iter()
+            .map(|enemy_type| format!("{:?}", enemy_type))
+            .collect::<Vec<String>>()
+            .join(", ");
+
+        let stage_name = commands
+            .spawn(TextBundle::from_section(
+                stage.name.clone(),
+                TextStyle {
+                    font: asset_server.load(FONT_BOLD),
+                    font_size: 36.0,
+                    color: Color::BLACK,
+                },
+            ))
+            .id();
+
+        let details = match locked_by {
+            Some(required) => format!("Survive {} to unlock", required),
+            None => format!(
+                "{}\n\nSurvive {:02}:{:02}\nEnemies: {}",
+                stage.description, minutes, seconds, enemies
+            ),
+        };
+
+        let stage_details = commands
+            .spawn(
+                TextBundle::from_section(
+                    details,
+                    TextStyle {
+                        font: asset_server.load(FONT),
+                        font_size: 16.0,
+                        color: Color::BLACK,
+                    },
+                )
+                .with_text_justify(JustifyText::Center),
+            )
+            .id();
+
+        commands
+            .entity(stage_button)
+            .push_children(&[stage_name, stage_details]);
+
+        commands
+            .entity(stage_select_popup)
+            .push_children(&[stage_button]);
+    }
+
+    commands
+        .entity(stage_select_parent)
+        .push_children(&[stage_select_popup]);
+}
+
+fn update_stage_select_button_interaction(
+    mut next_state: ResMut<NextState<GameState>>,
+    mut selected_stage: ResMut<SelectedStage>,
+    mut interaction_query: Query<
+        (&Interaction, &mut BackgroundColor, &StageSelectButton),
+        Changed<Interaction>,
+    >,
+) {
+    for (interaction, mut image, stage_button) in &mut interaction_query {
+        match *interaction {
+            Interaction::Pressed => {
+                selected_stage.0 = stage_button.stage_index;
+                next_state.set(GameState::Gameplay);
+            }
+            Interaction::Hovered => {
+                *image = HOVERED_BUTTON.into();
+            }
+            Interaction::None => {
+                *image = NORMAL_BUTTON.into();
+            }
+        }
+    }
+}
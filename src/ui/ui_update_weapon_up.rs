@@ -1,5 +1,5 @@
 use crate::components::*;
-use crate::constants::{FONT, FONT_BOLD, SCREEN_WIDTH};
+use crate::constants::{FONT, FONT_BOLD, UI_REFERENCE_ZOOM};
 use bevy::prelude::*;
 use rand::seq::SliceRandom;
 
@@ -36,7 +36,6 @@ fn spawn_update_weapon_ui(
     mut commands: Commands,
     asset_server: Res<AssetServer>,
     mut texture_atlas_layouts: ResMut<Assets<TextureAtlasLayout>>,
-    camera: Query<&Camera>,
     player_weapon: Res<PlayerWeapons>,
     player_upgrade_weapons: Res<PlayerUpgradeWeapons>,
 ) {
@@ -78,9 +77,6 @@ fn spawn_update_weapon_ui(
         .entity(weapon_update_parent)
         .push_children(&[weapon_update_popup]);
 
-    let camera = camera.single();
-    let view_dimensions = camera.logical_viewport_size().unwrap();
-    let ratio = view_dimensions.x / SCREEN_WIDTH as f32;
 
     let player_upgrades = player_upgrade_weapons.upgrades.clone();
 
@@ -120,7 +116,6 @@ fn spawn_update_weapon_ui(
         let card_item = card_ui_factory(
             &mut commands,
             &asset_server,
-            ratio,
             upgrade,
             &*item_name,
             &*item_description,
@@ -162,7 +157,6 @@ fn update_weapon_button_interaction(
 fn card_ui_factory(
     commands: &mut Commands,
     asset_server: &AssetServer,
-    ratio: f32,
     upgrade: &WeaponsUpgradesTypes,
     item_name: &str,
     item_description: &str,
@@ -174,8 +168,8 @@ fn card_ui_factory(
             ButtonBundle {
                 style: Style {
                     position_type: PositionType::Relative,
-                    width: Val::Px(80. * ratio),
-                    height: Val::Px(112. * ratio),
+                    width: Val::Px(80. * UI_REFERENCE_ZOOM),
+                    height: Val::Px(112. * UI_REFERENCE_ZOOM),
                     // horizontally center child text
                     justify_content: JustifyContent::Center,
                     // vertically center child text
@@ -1,2 +1,2 @@
+pub mod spawn_pattern;
 pub mod waves;
-pub mod waves_map_1;
@@ -0,0 +1,102 @@
+use crate::components::*;
+use crate::constants::{SCREEN_HEIGHT, SCREEN_WIDTH};
+use crate::math_utils::get_random_position_outside_screen;
+use bevy::prelude::*;
+use rand::Rng;
+use std::f32::consts::TAU;
+
+// distance from the screen border where groups appear
+const EDGE_MARGIN: f32 = 60.0;
+
+pub struct PatternSpawn {
+    pub position: Vec2,
+    pub direction: Option<Vec2>,
+}
+
+// Positions of one group of enemies around the player
+pub fn spawn_pattern_positions(
+    spawn_pattern: SpawnPattern,
+    amount: u32,
+    player_position: Vec2,
+    spawn_exclusion_zones: &SpawnExclusionZones,
+) -> Vec<PatternSpawn> {
+    let mut rng = rand::thread_rng();
+
+    let spawns: Vec<PatternSpawn> = match spawn_pattern {
+        SpawnPattern::Random => {
+            return (0..amount)
+                .map(|_| PatternSpawn {
+                    position: get_random_position_outside_screen(
+                        player_position,
+                        spawn_exclusion_zones,
+                    ),
+                    direction: None,
+                })
+                .collect();
+        }
+        SpawnPattern::Ring { radius } => {
+            let angle_offset = rng.gen_range(0.0..TAU);
+            (0..amount)
+                .map(|index| {
+                    let angle = angle_offset + TAU * index as f32 / amount as f32;
+                    PatternSpawn {
+                        position: player_position + Vec2::from_angle(angle) * radius,
+                        direction: None,
+                    }
+                })
+                .collect()
+        }
+        SpawnPattern::Wall { spacing } => {
+            let (edge_center, inward, along) = random_screen_edge();
+            (0..amount)
+                .map(|index| {
+                    let offset = (index as f32 - (amount - 1) as f32 / 2.0) * spacing;
+                    PatternSpawn {
+                        position: player_position + edge_center + along * offset,
+                        direction: Some(inward),
+                    }
+                })
+                .collect()
+        }
+        SpawnPattern::Swarm { spread } | SpawnPattern::Pack { spread } => {
+            let (edge_center, inward, along) = random_screen_edge();
+            // not always in line with the player
+            let half_edge = along.abs().dot(screen_half_size());
+            let group_center = player_position
+                + edge_center
+                + along * rng.gen_range(-half_edge / 2.0..half_edge / 2.0);
+            let direction = match spawn_pattern {
+                SpawnPattern::Swarm { .. } => Some(inward),
+                _ => None,
+            };
+            (0..amount)
+                .map(|_| PatternSpawn {
+                    position: group_center
+                        + Vec2::from_angle(rng.gen_range(0.0..TAU)) * rng.gen_range(0.0..spread),
+                    direction,
+                })
+                .collect()
+        }
+    };
+
+    // the group has holes where the map has obstacles
+    spawns
+        .into_iter()
+        .filter(|spawn| !spawn_exclusion_zones.contains(spawn.position))
+        .collect()
+}
+
+fn screen_half_size() -> Vec2 {
+    Vec2::new(SCREEN_WIDTH as f32 / 2.0, SCREEN_HEIGHT as f32 / 2.0)
+}
+
+// Middle of one side just outside the screen, direction toward the screen and direction along the side
+fn random_screen_edge() -> (Vec2, Vec2, Vec2) {
+    let half_size = screen_half_size() + EDGE_MARGIN;
+    match rand::thread_rng().gen_range(0..4) {
+        0 => (Vec2::new(0.0, -half_size.y), Vec2::Y, Vec2::X),
+        1 => (Vec2::new(0.0, half_size.y), Vec2::NEG_Y, Vec2::X),
+        2 => (Vec2::new(-half_size.x, 0.0), Vec2::X, Vec2::Y),
+        _ => (Vec2::new(half_size.x, 0.0), Vec2::NEG_X, Vec2::Y),
+    }
+}
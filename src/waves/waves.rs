@@ -1,6 +1,8 @@
 use crate::components::*;
+use crate::waves::spawn_pattern::spawn_pattern_positions;
 use bevy::prelude::*;
 use bevy::time::Stopwatch;
+use std::fs;
 
 pub struct WavesPlugin;
 
@@ -9,14 +11,59 @@ impl Plugin for WavesPlugin {
         app.insert_resource(WaveManagerGlobalTime {
             global_time: Stopwatch::new(),
         });
+        // waves come from the stage picked by the player
+        app.add_systems(OnExit(GameState::StageSelect), setup_stage_waves);
         app.add_systems(
             Update,
-            (waves_manager_tick, waves_spawn, global_timer_tick)
+            (
+                waves_manager_tick,
+                waves_spawn,
+                global_timer_tick,
+                stage_duration_check,
+            )
                 .run_if(in_state(GameState::Gameplay)),
         );
     }
 }
 
+fn setup_stage_waves(
+    mut commands: Commands,
+    stages: Res<StagesResource>,
+    selected_stage: Res<SelectedStage>,
+) {
+    let stage = &stages.stages[selected_stage.0];
+    let data = fs::read_to_string(&stage.waves_path).unwrap();
+    let waves_data: WavesData = ron::from_str(&data).unwrap();
+
+    for wave_manager in waves_data.wave_managers.iter() {
+        let waves_prefab: Vec<Wave> = wave_manager
+            .waves
+            .iter()
+            .filter(|wave| {
+                let is_in_roster = stage.enemy_roster.contains(&wave.enemy_type);
+                if !is_in_roster {
+                    println!(
+                        "{:?} is not in the roster of {}, wave ignored",
+                        wave.enemy_type, stage.name
+                    );
+                }
+                is_in_roster
+            })
+            .map(|wave| wave.to_wave())
+            .collect();
+
+        commands.spawn((
+            WaveManager {
+                start_timer: Timer::from_seconds(wave_manager.start, TimerMode::Once),
+                end_timer: Timer::from_seconds(wave_manager.end, TimerMode::Once),
+                waves_prefab,
+                waves: Vec::new(),
+            },
+            Name::new(wave_manager.name.clone()),
+        ));
+    }
+}
+
 fn waves_manager_tick(mut commands: Commands, mut waves: Query<&mut WaveManager>, time: Res<Time>) {
     for mut wave_manager in &mut waves {
         wave_manager.start_timer.tick(time.delta());
@@ -40,21 +87,78 @@ fn waves_spawn(
     mut waves: Query<&mut Wave>,
     time: Res<Time>,
     mut spawn_enemy: EventWriter<OnSpawnEnemy>,
+    player: Query<&Transform, With<Player>>,
+    spawn_exclusion_zones: Res<SpawnExclusionZones>,
+    difficulty_scaling: Res<DifficultyScaling>,
 ) {
+    let player_position = player.single().translation.truncate();
     for mut wave in &mut waves {
         wave.delay_between_spawn.tick(time.delta());
         if !wave.delay_between_spawn.just_finished() {
             continue;
         }
 
-        for _ in 0..wave.amount_per_timer_trigger {
+        // waves spawning once are bosses and set pieces, keep their size
+        let amount = match wave.delay_between_spawn.mode() {
+            TimerMode::Once => wave.amount_per_timer_trigger,
+            TimerMode::Repeating => (wave.amount_per_timer_trigger as f32
+                * difficulty_scaling.spawn_amount)
+                .round() as u32,
+        };
+
+        for spawn in spawn_pattern_positions(
+            wave.spawn_pattern,
+            amount,
+            player_position,
+            &spawn_exclusion_zones,
+        ) {
             spawn_enemy.send(OnSpawnEnemy {
                 enemy_types: wave.enemy_type,
+                position: Some(spawn.position),
+                direction: spawn.direction,
             });
         }
     }
 }
 
+// Once the stage duration is reached the waves stop and the reaper comes for the player
+fn stage_duration_check(
+    mut commands: Commands,
+    stages: Res<StagesResource>,
+    selected_stage: Res<SelectedStage>,
+    global_timer: Res<WaveManagerGlobalTime>,
+    wave_managers: Query<(Entity, &WaveManager)>,
+    mut spawn_enemy: EventWriter<OnSpawnEnemy>,
+    mut run_statistics: ResMut<RunStatistics>,
+) {
+    if run_statistics.outcome != RunOutcome::InProgress {
+        return;
+    }
+    let stage = &stages.stages[selected_stage.0];
+    if global_timer.global_time.elapsed_secs() < stage.duration {
+        return;
+    }
+
+    for (entity, wave_manager) in &wave_managers {
+        // waves of finished managers are already gone
+        for wave in wave_manager.waves.iter() {
+            if let Some(wave) = commands.get_entity(*wave) {
+                wave.despawn_recursive();
+            }
+        }
+        commands.entity(entity).despawn_recursive();
+    }
+    run_statistics.outcome = RunOutcome::Survived;
+
+    if let Some(reaper) = stage.reaper {
+        spawn_enemy.send(OnSpawnEnemy {
+            enemy_types: reaper,
+            position: None,
+            direction: None,
+        });
+    }
+}
+
 fn global_timer_tick(mut global_timer: ResMut<WaveManagerGlobalTime>, time: Res<Time>) {
     global_timer.global_time.tick(time.delta());
 }
@@ -1,6 +1,7 @@
 use crate::components::*;
-use crate::constants::PROJECTILE_Z_INDEX;
+use crate::constants::{EXPLOSION_SHAKE_COOLDOWN, EXPLOSION_TRAUMA, PROJECTILE_Z_INDEX};
 use crate::math_utils::{find_circle_circle_intersections, find_closest};
+use crate::pools::pool::{ProjectileCommands, TextureAtlasLayouts};
 use bevy::prelude::*;
 use bevy_rapier2d::prelude::*;
 use rand::prelude::SliceRandom;
@@ -27,6 +28,7 @@ impl Plugin for ArcaneMissilePlugin {
                 spawn_attack,
                 handle_arcane_missile_split_on_hit.run_if(run_if_upgrade_split_is_present),
                 handle_arcane_missile_explosion_hit.run_if(run_if_upgrade_explosion_is_present),
+                apply_freeze_on_explosion_hit,
             )
                 .run_if(in_state(GameState::Gameplay)),
         );
@@ -75,8 +77,8 @@ fn spawn_weapon(mut commands: Commands, player_stats: Res<PlayerInGameStats>) {
 }
 
 fn spawn_attack(
-    mut commands: Commands,
-    mut texture_atlas_layouts: ResMut<Assets<TextureAtlasLayout>>,
+    mut commands: ProjectileCommands,
+    mut texture_atlas_layouts: TextureAtlasLayouts,
     asset_server: Res<AssetServer>,
     mut player: Query<&Transform, With<Player>>,
     mut spawner: Query<
@@ -112,9 +114,8 @@ fn spawn_attack(
         );
 
         if let Some(closed_enemy) = closed_enemy {
-            let texture = asset_server.load("arcane-missile.png");
-            let layout = TextureAtlasLayout::from_grid(Vec2::new(100.0, 100.0), 30, 1, None, None);
-            let texture_atlas_layout = texture_atlas_layouts.add(layout);
+            let sheet = texture_atlas_layouts.aseprite("arcane-missile.json");
+            let texture = asset_server.load(&sheet.texture_path);
 
             if let Ok((entity, enemy_transform)) = enemies.get(closed_enemy) {
                 let distance_enemy_player = enemy_transform
@@ -140,37 +141,34 @@ fn spawn_attack(
                 commands.entity(spawner_entity).remove::<CanAttack>();
 
                 let projectile_id = commands
-                    .spawn((
-                        SpriteBundle {
-                            texture,
-                            transform: Transform {
-                                translation: Vec3::new(
-                                    player_transform.translation.x,
-                                    player_transform.translation.y,
-                                    PROJECTILE_Z_INDEX,
-                                ),
-                                scale: Vec3::splat(player_stats.area),
+                    .spawn_projectile(
+                        ProjectileTypes::ArcaneMissile,
+                        (
+                            SpriteBundle {
+                                texture,
+                                transform: Transform {
+                                    translation: Vec3::new(
+                                        player_transform.translation.x,
+                                        player_transform.translation.y,
+                                        PROJECTILE_Z_INDEX,
+                                    ),
+                                    scale: Vec3::splat(player_stats.area),
+                                    ..default()
+                                },
                                 ..default()
                             },
-                            ..default()
-                        },
-                        TextureAtlas {
-                            layout: texture_atlas_layout,
-                            index: 0,
-                        },
-                        AnimationIndices {
-                            first: 0,
-                            last: 1,
-                            is_repeating: true,
-                        },
-                        AnimationTimer(Timer::from_seconds(0.1, TimerMode::Repeating)),
-                        Sensor,
-                        Collider::ball(18.0 / 2.0),
-                        ArcaneMissile,
-                    ))
+                            TextureAtlas {
+                                layout: sheet.layout.clone(),
+                                index: 0,
+                            },
+                            SpriteAnimation::new(sheet.clone(), "fly"),
+                            Sensor,
+                            Collider::ball(18.0 / 2.0),
+                            ArcaneMissile,
+                        ),
+                    )
                     .insert((
                         Projectile,
-                        ProjectileType(ProjectileTypes::ArcaneMissile),
                         ProjectileDamage(50.0),
                         ProjectileTarget(entity),
                         ProjectileOrigin(player_transform.translation),
@@ -209,8 +207,8 @@ fn spawn_attack(
 }
 
 fn handle_arcane_missile_split_on_hit(
-    mut commands: Commands,
-    mut texture_atlas_layouts: ResMut<Assets<TextureAtlasLayout>>,
+    mut commands: ProjectileCommands,
+    mut texture_atlas_layouts: TextureAtlasLayouts,
     asset_server: Res<AssetServer>,
     mut enemies: Query<(Entity, &Transform), With<Enemy>>,
     mut eneny_hit_event: EventReader<OnEnemyHit>,
@@ -237,9 +235,8 @@ fn handle_arcane_missile_split_on_hit(
                 }
             };
 
-            let texture = asset_server.load("arcane-missile.png");
-            let layout = TextureAtlasLayout::from_grid(Vec2::new(100.0, 100.0), 30, 1, None, None);
-            let texture_atlas_layout = texture_atlas_layouts.add(layout);
+            let sheet = texture_atlas_layouts.aseprite("arcane-missile.json");
+            let texture = asset_server.load(&sheet.texture_path);
 
             if let Ok((entity, enemy_transform)) = enemies.get(picked_enemy) {
                 let distance_enemy_projectiler = enemy_transform
@@ -260,37 +257,34 @@ fn handle_arcane_missile_split_on_hit(
                 };
 
                 let projectile_id = commands
-                    .spawn((
-                        SpriteBundle {
-                            texture,
-                            transform: Transform {
-                                translation: Vec3::new(
-                                    event.projectile_position.x,
-                                    event.projectile_position.y,
-                                    PROJECTILE_Z_INDEX,
-                                ),
-                                scale: Vec3::splat(player_stats.area),
+                    .spawn_projectile(
+                        ProjectileTypes::ArcaneMissileSplit,
+                        (
+                            SpriteBundle {
+                                texture,
+                                transform: Transform {
+                                    translation: Vec3::new(
+                                        event.projectile_position.x,
+                                        event.projectile_position.y,
+                                        PROJECTILE_Z_INDEX,
+                                    ),
+                                    scale: Vec3::splat(player_stats.area),
+                                    ..default()
+                                },
                                 ..default()
                             },
-                            ..default()
-                        },
-                        TextureAtlas {
-                            layout: texture_atlas_layout,
-                            index: 0,
-                        },
-                        AnimationIndices {
-                            first: 0,
-                            last: 1,
-                            is_repeating: true,
-                        },
-                        AnimationTimer(Timer::from_seconds(0.1, TimerMode::Repeating)),
-                        Sensor,
-                        Collider::ball(18.0 / 2.0),
-                        ArcaneMissile,
-                    ))
+                            TextureAtlas {
+                                layout: sheet.layout.clone(),
+                                index: 0,
+                            },
+                            SpriteAnimation::new(sheet.clone(), "fly"),
+                            Sensor,
+                            Collider::ball(18.0 / 2.0),
+                            ArcaneMissile,
+                        ),
+                    )
                     .insert((
                         Projectile,
-                        ProjectileType(ProjectileTypes::ArcaneMissileSplit),
                         ProjectileDamage(25.0),
                         ProjectileTarget(entity),
                         ProjectileOrigin(event.projectile_position),
@@ -329,13 +323,17 @@ fn handle_arcane_missile_split_on_hit(
 }
 
 fn handle_arcane_missile_explosion_hit(
-    mut commands: Commands,
-    mut texture_atlas_layouts: ResMut<Assets<TextureAtlasLayout>>,
+    mut commands: ProjectileCommands,
+    mut texture_atlas_layouts: TextureAtlasLayouts,
     asset_server: Res<AssetServer>,
     mut eneny_hit_event: EventReader<OnEnemyHit>,
     player_stats: Res<PlayerInGameStats>,
     weapon_upgrades: Res<PlayerUpgradeWeapons>,
+    mut camera_shake_event: EventWriter<OnCameraShake>,
+    time: Res<Time>,
+    mut shake_cooldown: Local<f32>,
 ) {
+    *shake_cooldown -= time.delta_seconds();
     for event in eneny_hit_event.read() {
         if event.projectile_type != ProjectileTypes::ArcaneMissile
             && event.projectile_type != ProjectileTypes::ArcaneMissileSplit
@@ -343,50 +341,52 @@ fn handle_arcane_missile_explosion_hit(
             continue;
         }
 
-        let texture = asset_server.load("arcane-missile-explosion.png");
-        let layout = TextureAtlasLayout::from_grid(Vec2::new(64.0, 64.0), 11, 1, None, None);
-        let texture_atlas_layout = texture_atlas_layouts.add(layout);
+        // several missiles a second would keep the screen shaking
+        if *shake_cooldown <= 0.0 {
+            camera_shake_event.send(OnCameraShake {
+                trauma: EXPLOSION_TRAUMA,
+            });
+            *shake_cooldown = EXPLOSION_SHAKE_COOLDOWN;
+        }
+
+        let sheet = texture_atlas_layouts.aseprite("arcane-missile-explosion.json");
+        let texture = asset_server.load(&sheet.texture_path);
 
         let projectile_id = commands
-            .spawn((
-                SpriteBundle {
-                    texture,
-                    transform: Transform {
-                        translation: Vec3::new(
-                            event.projectile_position.x,
-                            event.projectile_position.y,
-                            PROJECTILE_Z_INDEX,
-                        ),
-                        scale: Vec3::splat(player_stats.area),
+            .spawn_projectile(
+                ProjectileTypes::ArcaneMissileExplosion,
+                (
+                    SpriteBundle {
+                        texture,
+                        transform: Transform {
+                            translation: Vec3::new(
+                                event.projectile_position.x,
+                                event.projectile_position.y,
+                                PROJECTILE_Z_INDEX,
+                            ),
+                            scale: Vec3::splat(player_stats.area),
+                            ..default()
+                        },
                         ..default()
                     },
-                    ..default()
-                },
-                TextureAtlas {
-                    layout: texture_atlas_layout,
-                    index: 0,
-                },
-                AnimationIndices {
-                    first: 0,
-                    last: 10,
-                    is_repeating: false,
-                },
-                AnimationTimer(Timer::from_seconds(0.05, TimerMode::Repeating)),
-                Sensor,
-                Collider::ball(55.0 / 2.0),
-                ArcaneMissile,
-            ))
+                    TextureAtlas {
+                        layout: sheet.layout.clone(),
+                        index: 0,
+                    },
+                    SpriteAnimation::new(sheet.clone(), "explode"),
+                    Sensor,
+                    Collider::ball(55.0 / 2.0),
+                    ArcaneMissile,
+                ),
+            )
             .insert((
                 Projectile,
-                ProjectileType(ProjectileTypes::ArcaneMissileExplosion),
                 ProjectileDamage(80.0),
                 ProjectileOrigin(event.projectile_position),
                 ProjectileImpulse(120.0),
                 AlreadyHitEnemies { seen: Vec::new() },
                 ProjectilePierce,
-                ProjectileLifetime {
-                    timer: Timer::from_seconds(0.4, TimerMode::Once),
-                },
+                AnimationFinishedAction::DeleteProjectile,
                 ProjectileBundleCollider::default(),
                 Name::new("Arcane missile explosion"),
             ))
@@ -403,3 +403,23 @@ fn handle_arcane_missile_explosion_hit(
         // }
     }
 }
+
+fn apply_freeze_on_explosion_hit(
+    enemies: Query<Entity, With<Enemy>>,
+    mut eneny_hit_event: EventReader<OnEnemyHit>,
+    mut apply_status_effect: EventWriter<OnApplyStatusEffect>,
+) {
+    for event in eneny_hit_event.read() {
+        if event.projectile_type != ProjectileTypes::ArcaneMissileExplosion {
+            continue;
+        }
+        if let Ok(enemy_entity) = enemies.get(event.enemy_entity) {
+            apply_status_effect.send(OnApplyStatusEffect {
+                enemy_entity,
+                effect_type: StatusEffectTypes::Freeze,
+                value: 0.0,
+                duration: 1.0,
+            });
+        }
+    }
+}
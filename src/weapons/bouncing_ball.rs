@@ -1,6 +1,7 @@
 use crate::components::*;
 use crate::constants::PROJECTILE_Z_INDEX;
 use crate::math_utils::find_closest;
+use crate::pools::pool::ProjectileCommands;
 use bevy::prelude::*;
 use bevy_rapier2d::prelude::*;
 
@@ -53,9 +54,9 @@ fn spawn_weapon(mut commands: Commands, player_stats: Res<PlayerInGameStats>) {
 }
 
 fn spawn_attack(
-    mut commands: Commands,
+    mut commands: ProjectileCommands,
     asset_server: Res<AssetServer>,
-    mut player: Query<&Transform, With<Player>>,
+    mut player: Query<(&Transform, &Player)>,
     mut spawner: Query<
         (Entity, &mut AttackAmmo),
         (
@@ -67,28 +68,38 @@ fn spawn_attack(
     mut enemies: Query<(Entity, &Transform), With<Enemy>>,
     player_stats: Res<PlayerInGameStats>,
 ) {
-    let player_transform = player.single_mut();
+    let (player_transform, player) = player.single_mut();
 
     if let Ok((spawner_entity, mut attack_ammo)) = spawner.get_single_mut() {
-        let mut enemies_lens = enemies.transmute_lens::<(Entity, &Transform)>();
-        let closed_enemy: Option<Entity> = find_closest(
-            player_transform.translation,
-            enemies_lens.query(),
-            300.0,
-            None,
-        );
+        // manual aim shoots right away, otherwise wait for an enemy in range
+        let direction = match player.aim {
+            Some(aim) => Some(-aim),
+            None => {
+                let mut enemies_lens = enemies.transmute_lens::<(Entity, &Transform)>();
+                let closed_enemy: Option<Entity> = find_closest(
+                    player_transform.translation,
+                    enemies_lens.query(),
+                    300.0,
+                    None,
+                );
+                closed_enemy
+                    .and_then(|closed_enemy| enemies.get(closed_enemy).ok())
+                    .map(|(_enemy, enemy_transform)| {
+                        (player_transform.translation.xy() - enemy_transform.translation.xy())
+                            .normalize()
+                    })
+            }
+        };
 
-        if let Some(closed_enemy) = closed_enemy {
-            if let Ok((_enemy, enemy_transform)) = enemies.get(closed_enemy) {
-                attack_ammo.amount -= 1;
-                commands.entity(spawner_entity).remove::<CanAttack>();
+        if let Some(direction) = direction {
+            attack_ammo.amount -= 1;
+            commands.entity(spawner_entity).remove::<CanAttack>();
 
-                let texture = asset_server.load("bouncing_ball.png");
-                let direction = (player_transform.translation.xy()
-                    - enemy_transform.translation.xy())
-                .normalize();
-                commands
-                    .spawn((
+            let texture = asset_server.load("bouncing_ball.png");
+            commands
+                .spawn_projectile(
+                    ProjectileTypes::BouncingBall,
+                    (
                         SpriteBundle {
                             texture,
                             transform: Transform {
@@ -114,22 +125,21 @@ fn spawn_attack(
                         ProjectileDamage(50.0),
                         ProjectilePierce,
                         BouncingBall,
-                    ))
-                    .insert((
-                        Projectile,
-                        ProjectileSpeed(100.0),
-                        ProjectileDirection(direction),
-                        ProjectileImpulse(700.0),
-                        ProjectileType(ProjectileTypes::BouncingBall),
-                        Name::new("Bouncing ball Attack"),
-                    ));
-            }
+                    ),
+                )
+                .insert((
+                    Projectile,
+                    ProjectileSpeed(100.0),
+                    ProjectileDirection(direction),
+                    ProjectileImpulse(700.0),
+                    Name::new("Bouncing ball Attack"),
+                ));
         }
     }
 }
 
 fn duplicate_ball_on_hit(
-    mut commands: Commands,
+    mut commands: ProjectileCommands,
     asset_server: Res<AssetServer>,
     enemies: Query<(Entity, &Transform), With<Enemy>>,
     mut eneny_hit_event: EventReader<OnEnemyHit>,
@@ -145,34 +155,36 @@ fn duplicate_ball_on_hit(
                 let direction =
                     (enemy_transform.translation.xy() - event.projectile_position.xy()).normalize();
                 commands
-                    .spawn((
-                        SpriteBundle {
-                            texture,
-                            transform: Transform {
-                                translation: event.projectile_position,
+                    .spawn_projectile(
+                        ProjectileTypes::BouncingBallSplit,
+                        (
+                            SpriteBundle {
+                                texture,
+                                transform: Transform {
+                                    translation: event.projectile_position,
+                                    ..default()
+                                },
                                 ..default()
                             },
-                            ..default()
-                        },
-                        Sensor,
-                        Collider::ball(16.0 / 2.0),
-                        ProjectileBundleCollider::default(),
-                        ProjectileLifetime {
-                            timer: Timer::from_seconds(
-                                8.0 * player_stats.attack_duration,
-                                TimerMode::Once,
-                            ),
-                        },
-                        ProjectileDamage(25.0),
-                        ProjectilePierce,
-                        BouncingBall,
-                    ))
+                            Sensor,
+                            Collider::ball(16.0 / 2.0),
+                            ProjectileBundleCollider::default(),
+                            ProjectileLifetime {
+                                timer: Timer::from_seconds(
+                                    8.0 * player_stats.attack_duration,
+                                    TimerMode::Once,
+                                ),
+                            },
+                            ProjectileDamage(25.0),
+                            ProjectilePierce,
+                            BouncingBall,
+                        ),
+                    )
                     .insert((
                         Projectile,
                         ProjectileSpeed(100.0),
                         ProjectileDirection(direction),
                         ProjectileImpulse(700.0),
-                        ProjectileType(ProjectileTypes::BouncingBallSplit),
                         Name::new("Bouncing ball duplicate Attack"),
                     ));
             }
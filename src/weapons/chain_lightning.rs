@@ -1,6 +1,7 @@
 use crate::components::*;
 use crate::constants::PROJECTILE_Z_INDEX;
 use crate::math_utils::find_closest;
+use crate::pools::pool::{ProjectileCommands, TextureAtlasLayouts};
 use bevy::prelude::*;
 use bevy::sprite::Anchor;
 use std::f32::consts::PI;
@@ -87,8 +88,8 @@ fn handle_chain_lightning_extra_ammo_update(
 }
 
 fn spawn_attack(
-    mut commands: Commands,
-    mut texture_atlas_layouts: ResMut<Assets<TextureAtlasLayout>>,
+    mut commands: ProjectileCommands,
+    mut texture_atlas_layouts: TextureAtlasLayouts,
     asset_server: Res<AssetServer>,
     mut player: Query<&Transform, With<Player>>,
     mut spawner: Query<
@@ -126,15 +127,8 @@ fn spawn_attack(
         };
 
         for _ in 0..amount_of_new_chain {
-            let texture = asset_server.load("lightning_strike.png");
-            let layout = TextureAtlasLayout::from_grid(
-                Vec2::new(16.0, 32.0),
-                5,
-                1,
-                Option::from(Vec2::new(0.0, 0.0)),
-                None,
-            );
-            let texture_atlas_layout = texture_atlas_layouts.add(layout);
+            let sheet = texture_atlas_layouts.aseprite("lightning_strike.json");
+            let texture = asset_server.load(&sheet.texture_path);
 
             let mut chain_lightning_ammo = attack_ammo.amount.clone();
 
@@ -167,48 +161,46 @@ fn spawn_attack(
                             .distance(position_lightning.xy());
                         let scale_y = lightning_distance / 32.0;
 
-                        commands.spawn((
-                            SpriteBundle {
-                                texture: texture.clone(),
-                                transform: Transform {
-                                    translation: Vec3::new(
-                                        position_lightning.x,
-                                        position_lightning.y,
-                                        PROJECTILE_Z_INDEX,
-                                    ),
-                                    rotation: Quat::from_rotation_z(
-                                        lightning_direction.to_angle() - PI / 2.0,
-                                    ),
-                                    scale: Vec3::new(1.0, scale_y, 1.0),
+                        commands.spawn_projectile(
+                            ProjectileTypes::ChainLightning,
+                            (
+                                SpriteBundle {
+                                    texture: texture.clone(),
+                                    transform: Transform {
+                                        translation: Vec3::new(
+                                            position_lightning.x,
+                                            position_lightning.y,
+                                            PROJECTILE_Z_INDEX,
+                                        ),
+                                        rotation: Quat::from_rotation_z(
+                                            lightning_direction.to_angle() - PI / 2.0,
+                                        ),
+                                        scale: Vec3::new(1.0, scale_y, 1.0),
+                                        ..default()
+                                    },
+                                    sprite: Sprite {
+                                        anchor: Anchor::BottomCenter,
+                                        ..default()
+                                    },
                                     ..default()
                                 },
-                                sprite: Sprite {
-                                    anchor: Anchor::BottomCenter,
-                                    ..default()
+                                // ImageScaleMode::Tiled {
+                                //     tile_y: true,
+                                //     tile_x: false,
+                                //     stretch_value: 1.0,
+                                // },
+                                TextureAtlas {
+                                    layout: sheet.layout.clone(),
+                                    index: 0,
+                                },
+                                SpriteAnimation::new(sheet.clone(), "strike"),
+                                Projectile,
+                                ProjectileFixedScale,
+                                ProjectileLifetime {
+                                    timer: Timer::from_seconds(0.5, TimerMode::Once),
                                 },
-                                ..default()
-                            },
-                            // ImageScaleMode::Tiled {
-                            //     tile_y: true,
-                            //     tile_x: false,
-                            //     stretch_value: 1.0,
-                            // },
-                            TextureAtlas {
-                                layout: texture_atlas_layout.clone(),
-                                index: 0,
-                            },
-                            AnimationIndices {
-                                first: 0,
-                                last: 4,
-                                is_repeating: true,
-                            },
-                            AnimationTimer(Timer::from_seconds(0.1, TimerMode::Repeating)),
-                            Projectile,
-                            ProjectileFixedScale,
-                            ProjectileLifetime {
-                                timer: Timer::from_seconds(0.5, TimerMode::Once),
-                            },
-                        ));
+                            ),
+                        );
 
                         // move position to the one from the enemy
                         position_lightning = enemy_transform.translation.clone();
@@ -230,17 +222,20 @@ fn spawn_attack(
 }
 
 fn apply_stun_on_hit(
-    mut commands: Commands,
     enemies: Query<Entity, With<Enemy>>,
     mut eneny_hit_event: EventReader<OnEnemyHit>,
+    mut apply_status_effect: EventWriter<OnApplyStatusEffect>,
 ) {
     for event in eneny_hit_event.read() {
         if event.projectile_type != ProjectileTypes::ChainLightning {
             continue;
         }
         if let Ok(enemy_entity) = enemies.get(event.enemy_entity) {
-            commands.entity(enemy_entity).try_insert(StunAura {
-                lifetime: Timer::from_seconds(2.0, TimerMode::Once),
+            apply_status_effect.send(OnApplyStatusEffect {
+                enemy_entity,
+                effect_type: StatusEffectTypes::Stun,
+                value: 0.0,
+                duration: 2.0,
             });
         }
     }
@@ -1,5 +1,7 @@
 use crate::components::*;
 use crate::constants::PROJECTILE_Z_INDEX;
+use crate::math_utils::sprite_flip_and_rotation;
+use crate::pools::pool::{ProjectileCommands, TextureAtlasLayouts};
 use bevy::prelude::*;
 use bevy_rapier2d::prelude::*;
 
@@ -32,7 +34,13 @@ impl Plugin for WeaponClawPlugin {
         // );
         app.add_systems(
             Update,
-            (spawn_attack, spawn_attack_upgrade).run_if(in_state(GameState::Gameplay)),
+            (
+                spawn_attack,
+                spawn_attack_upgrade,
+                claw_enable_hitbox,
+                apply_poison_on_hit,
+            )
+                .run_if(in_state(GameState::Gameplay)),
         );
     }
 }
@@ -71,7 +79,7 @@ fn spawn_weapon(mut commands: Commands, player_stats: Res<PlayerInGameStats>) {
 }
 
 fn spawn_attack(
-    mut commands: Commands,
+    mut commands: ProjectileCommands,
     asset_server: Res<AssetServer>,
     mut spawner: Query<
         (Entity, &mut AttackAmmo, &mut ProjectileBendLeftOrRight),
@@ -81,37 +89,25 @@ fn spawn_attack(
             Without<AttackSpawnerIsReloading>,
         ),
     >,
-    mut player: Query<&Transform, With<Player>>,
-    mut texture_atlas_layouts: ResMut<Assets<TextureAtlasLayout>>,
+    mut player: Query<(&Transform, &Player)>,
+    mut texture_atlas_layouts: TextureAtlasLayouts,
     player_stats: Res<PlayerInGameStats>,
 ) {
-    let player_transform = player.single_mut();
+    let (player_transform, player) = player.single_mut();
 
     if let Ok((spawner_entity, mut attack_ammo, mut projectile_orientation)) =
         spawner.get_single_mut()
     {
-        let texture = asset_server.load("claw.png");
-        let layout = TextureAtlasLayout::from_grid(
-            Vec2::new(48.0, 48.0),
-            2,
-            1,
-            Option::from(Vec2::new(1.0, 1.0)),
-            None,
-        );
-        let texture_atlas_layout = texture_atlas_layouts.add(layout);
+        let sheet = texture_atlas_layouts.aseprite("claw.json");
+        let texture = asset_server.load(&sheet.texture_path);
 
-        let mut pos_x = player_transform.translation.x;
-
-        let is_flip = match projectile_orientation.0 {
-            true => {
-                pos_x -= CLAWS_OFFSET;
-                true
-            }
-            false => {
-                pos_x += CLAWS_OFFSET;
-                false
-            }
+        // in front of the player then behind, in any of the eight directions
+        let direction = match projectile_orientation.0 {
+            true => player.aim_direction(),
+            false => -player.aim_direction(),
         };
+        let position = player_transform.translation.truncate() + direction * CLAWS_OFFSET;
+        let (is_flip, rotation) = sprite_flip_and_rotation(direction);
 
         **projectile_orientation = !projectile_orientation.0;
 
@@ -119,38 +115,34 @@ fn spawn_attack(
         commands.entity(spawner_entity).remove::<CanAttack>();
 
         commands
-            .spawn((
-                SpriteBundle {
-                    texture,
-                    transform: Transform {
-                        translation: Vec3::new(
-                            pos_x,
-                            player_transform.translation.y,
-                            PROJECTILE_Z_INDEX,
-                        ),
-                        scale: Vec3::splat(player_stats.area),
+            .spawn_projectile(
+                ProjectileTypes::Claw,
+                (
+                    SpriteBundle {
+                        texture,
+                        transform: Transform {
+                            translation: position.extend(PROJECTILE_Z_INDEX),
+                            rotation,
+                            scale: Vec3::splat(player_stats.area),
+                        },
+                        sprite: Sprite {
+                            flip_x: is_flip,
+                            ..default()
+                        },
                         ..default()
                     },
-                    sprite: Sprite {
-                        flip_x: is_flip,
-                        ..default()
+                    TextureAtlas {
+                        layout: sheet.layout.clone(),
+                        index: 0,
                     },
-                    ..default()
-                },
-                TextureAtlas {
-                    layout: texture_atlas_layout,
-                    index: 0,
-                },
-                AnimationIndices {
-                    first: 0,
-                    last: 1,
-                    is_repeating: false,
-                },
-                AnimationTimer(Timer::from_seconds(0.1, TimerMode::Repeating)),
-                Sensor,
-            ))
+                    SpriteAnimation::new(sheet.clone(), "swipe"),
+                    Sensor,
+                ),
+            )
             .insert((
                 ProjectileBundleCollider::default(),
+                // turned on by the hitbox event of the clip
+                ColliderDisabled,
                 Collider::cuboid(48.0 / 2.0, 48.0 / 2.0),
                 // Collider::convex_hull(&vec![
                 //     Vect::new(-20.0, 0.0),
@@ -166,7 +158,6 @@ fn spawn_attack(
                 ProjectileImpulse(2000.0),
                 Claw,
                 ProjectilePierce,
-                ProjectileType(ProjectileTypes::Claw),
                 Projectile,
                 Name::new("Claw Attack"),
             ));
@@ -192,7 +183,7 @@ fn spawn_weapon_upgrade(mut commands: Commands, player_stats: Res<PlayerInGameSt
 }
 
 fn spawn_attack_upgrade(
-    mut commands: Commands,
+    mut commands: ProjectileCommands,
     asset_server: Res<AssetServer>,
     mut spawner: Query<
         (Entity, &mut AttackAmmo),
@@ -203,21 +194,14 @@ fn spawn_attack_upgrade(
         ),
     >,
     mut player: Query<&Transform, With<Player>>,
-    mut texture_atlas_layouts: ResMut<Assets<TextureAtlasLayout>>,
+    mut texture_atlas_layouts: TextureAtlasLayouts,
     player_stats: Res<PlayerInGameStats>,
 ) {
     let player_transform = player.single_mut();
 
     if let Ok((spawner_entity, mut attack_ammo)) = spawner.get_single_mut() {
-        let texture = asset_server.load("claw.png");
-        let layout = TextureAtlasLayout::from_grid(
-            Vec2::new(48.0, 48.0),
-            4,
-            1,
-            Option::from(Vec2::new(1.0, 1.0)),
-            None,
-        );
-        let texture_atlas_layout = texture_atlas_layouts.add(layout);
+        let sheet = texture_atlas_layouts.aseprite("claw.json");
+        let texture = asset_server.load(&sheet.texture_path);
 
         commands.entity(spawner_entity).remove::<CanAttack>();
 
@@ -255,35 +239,34 @@ fn spawn_attack_upgrade(
             attack_ammo.amount -= 1;
 
             commands
-                .spawn((
-                    SpriteBundle {
-                        texture: texture.clone(),
-                        transform: Transform {
-                            translation: Vec3::new(pos_x, pos_y, PROJECTILE_Z_INDEX),
-                            scale: Vec3::splat(player_stats.area),
+                .spawn_projectile(
+                    ProjectileTypes::Claw,
+                    (
+                        SpriteBundle {
+                            texture: texture.clone(),
+                            transform: Transform {
+                                translation: Vec3::new(pos_x, pos_y, PROJECTILE_Z_INDEX),
+                                scale: Vec3::splat(player_stats.area),
+                                ..default()
+                            },
+                            sprite: Sprite {
+                                flip_x: is_flip,
+                                ..default()
+                            },
                             ..default()
                         },
-                        sprite: Sprite {
-                            flip_x: is_flip,
-                            ..default()
+                        TextureAtlas {
+                            layout: sheet.layout.clone(),
+                            index: 0,
                         },
-                        ..default()
-                    },
-                    TextureAtlas {
-                        layout: texture_atlas_layout.clone(),
-                        index: 0,
-                    },
-                    AnimationIndices {
-                        first: 0,
-                        last: 3,
-                        is_repeating: false,
-                    },
-                    AnimationTimer(Timer::from_seconds(0.1, TimerMode::Repeating)),
-                    Sensor,
-                ))
+                        SpriteAnimation::new(sheet.clone(), "swipe_big"),
+                        Sensor,
+                    ),
+                )
                 .insert((
                     Collider::cuboid(48.0 / 2.0, 48.0 / 2.0),
                     ProjectileBundleCollider::default(),
+                    ColliderDisabled,
                     ProjectileLifetime {
                         timer: Timer::from_seconds(
                             0.3 * player_stats.attack_duration,
@@ -295,10 +278,43 @@ fn spawn_attack_upgrade(
                     ProjectileImpulse(2000.0),
                     Claw,
                     ProjectilePierce,
-                    ProjectileType(ProjectileTypes::Claw),
                     Projectile,
                     Name::new("Claw Attack"),
                 ));
         }
     }
 }
+
+// The claw only hurts once the swing reaches the frame with the hitbox event
+fn claw_enable_hitbox(
+    mut commands: Commands,
+    mut animation_event: EventReader<OnAnimationEvent>,
+    claws: Query<(), With<Claw>>,
+) {
+    for event in animation_event.read() {
+        if event.name == "hitbox" && claws.contains(event.entity) {
+            commands.entity(event.entity).remove::<ColliderDisabled>();
+        }
+    }
+}
+
+fn apply_poison_on_hit(
+    enemies: Query<Entity, With<Enemy>>,
+    mut eneny_hit_event: EventReader<OnEnemyHit>,
+    mut apply_status_effect: EventWriter<OnApplyStatusEffect>,
+    player_stats: Res<PlayerInGameStats>,
+) {
+    for event in eneny_hit_event.read() {
+        if event.projectile_type != ProjectileTypes::Claw {
+            continue;
+        }
+        if let Ok(enemy_entity) = enemies.get(event.enemy_entity) {
+            apply_status_effect.send(OnApplyStatusEffect {
+                enemy_entity,
+                effect_type: StatusEffectTypes::Poison,
+                value: 4.0 * player_stats.power,
+                duration: 4.0,
+            });
+        }
+    }
+}
@@ -1,5 +1,6 @@
 use crate::components::*;
 use crate::constants::PROJECTILE_Z_INDEX;
+use crate::pools::pool::{ProjectileCommands, TextureAtlasLayouts};
 use bevy::prelude::*;
 use bevy_rapier2d::prelude::*;
 
@@ -37,9 +38,9 @@ fn spawn_weapon(mut commands: Commands, _player_stats: Res<PlayerInGameStats>) {
 }
 
 pub fn spawn_attack(
-    mut commands: Commands,
+    mut commands: ProjectileCommands,
     asset_server: Res<AssetServer>,
-    mut texture_atlas_layouts: ResMut<Assets<TextureAtlasLayout>>,
+    mut texture_atlas_layouts: TextureAtlasLayouts,
     player_transform: Query<&Transform, With<Player>>,
     spawner: Query<Entity, (With<FireAreaSpawner>, With<CanAttack>)>,
     player_stats: Res<PlayerInGameStats>,
@@ -47,52 +48,42 @@ pub fn spawn_attack(
     let player_transform = player_transform.single();
 
     if let Ok(spawner_entity) = spawner.get_single() {
-        let texture = asset_server.load("fire-area.png");
-        let layout = TextureAtlasLayout::from_grid(
-            Vec2::new(48.0, 48.0),
-            3,
-            1,
-            Option::from(Vec2::new(1.0, 0.0)),
-            None,
-        );
-        let texture_atlas_layout = texture_atlas_layouts.add(layout);
+        let sheet = texture_atlas_layouts.aseprite("fire-area.json");
+        let texture = asset_server.load(&sheet.texture_path);
 
         commands.entity(spawner_entity).remove::<CanAttack>();
 
         commands
-            .spawn((
-                SpriteBundle {
-                    texture,
-                    transform: Transform {
-                        translation: Vec3::new(
-                            player_transform.translation.x,
-                            player_transform.translation.y,
-                            PROJECTILE_Z_INDEX,
-                        ),
-                        scale: Vec3::splat(player_stats.area),
+            .spawn_projectile(
+                ProjectileTypes::FireArea,
+                (
+                    SpriteBundle {
+                        texture,
+                        transform: Transform {
+                            translation: Vec3::new(
+                                player_transform.translation.x,
+                                player_transform.translation.y,
+                                PROJECTILE_Z_INDEX,
+                            ),
+                            scale: Vec3::splat(player_stats.area),
+                            ..default()
+                        },
                         ..default()
                     },
-                    ..default()
-                },
-                TextureAtlas {
-                    layout: texture_atlas_layout,
-                    index: 0,
-                },
-                AnimationIndices {
-                    first: 0,
-                    last: 2,
-                    is_repeating: true,
-                },
-                AnimationTimer(Timer::from_seconds(0.1, TimerMode::Repeating)),
-                Sensor,
-                Collider::ball(48.0 / 2.0),
-                ProjectileBundleCollider::default(),
-            ))
+                    TextureAtlas {
+                        layout: sheet.layout.clone(),
+                        index: 0,
+                    },
+                    SpriteAnimation::new(sheet, "burn"),
+                    Sensor,
+                    Collider::ball(48.0 / 2.0),
+                    ProjectileBundleCollider::default(),
+                ),
+            )
             .insert((
                 FireArea,
                 Projectile,
                 ProjectileliveForever,
-                ProjectileType(ProjectileTypes::FireArea),
                 ProjectileImpulse(150.0),
                 ProjectileDamage(20.0),
                 ProjectilePositionOnPlayer,
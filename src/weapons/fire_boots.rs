@@ -1,5 +1,6 @@
 use crate::components::*;
 use crate::constants::PROJECTILE_Z_INDEX;
+use crate::pools::pool::{ProjectileCommands, TextureAtlasLayouts};
 use bevy::prelude::*;
 use bevy_rapier2d::prelude::*;
 
@@ -21,7 +22,7 @@ impl Plugin for FireBootsPlugin {
         );
         app.add_systems(
             Update,
-            (spawn_attack,).run_if(in_state(GameState::Gameplay)),
+            (spawn_attack, apply_burn_on_hit).run_if(in_state(GameState::Gameplay)),
         );
     }
 }
@@ -52,9 +53,9 @@ fn spawn_weapon(mut commands: Commands, player_stats: Res<PlayerInGameStats>) {
 }
 
 fn spawn_attack(
-    mut commands: Commands,
+    mut commands: ProjectileCommands,
     asset_server: Res<AssetServer>,
-    mut texture_atlas_layouts: ResMut<Assets<TextureAtlasLayout>>,
+    mut texture_atlas_layouts: TextureAtlasLayouts,
     mut player: Query<&Transform, With<Player>>,
     mut spawner: Query<
         (Entity, &mut AttackAmmo),
@@ -69,45 +70,41 @@ fn spawn_attack(
     let player_transform = player.single_mut();
 
     if let Ok((spawner_entity, mut attack_ammo)) = spawner.get_single_mut() {
-        let texture = asset_server.load("fire-boots.png");
-        let layout = TextureAtlasLayout::from_grid(Vec2::new(24.0, 24.0), 8, 1, None, None);
-        let texture_atlas_layout = texture_atlas_layouts.add(layout);
+        let sheet = texture_atlas_layouts.aseprite("fire-boots.json");
+        let texture = asset_server.load(&sheet.texture_path);
 
         attack_ammo.amount -= 1;
         commands.entity(spawner_entity).remove::<CanAttack>();
 
         commands
-            .spawn((
-                SpriteBundle {
-                    texture: texture.clone(),
-                    transform: Transform {
-                        translation: Vec3::new(
-                            player_transform.translation.x,
-                            player_transform.translation.y,
-                            PROJECTILE_Z_INDEX,
-                        ),
-                        scale: Vec3::splat(player_stats.area),
+            .spawn_projectile(
+                ProjectileTypes::FireBoots,
+                (
+                    SpriteBundle {
+                        texture: texture.clone(),
+                        transform: Transform {
+                            translation: Vec3::new(
+                                player_transform.translation.x,
+                                player_transform.translation.y,
+                                PROJECTILE_Z_INDEX,
+                            ),
+                            scale: Vec3::splat(player_stats.area),
+                            ..default()
+                        },
                         ..default()
                     },
-                    ..default()
-                },
-                TextureAtlas {
-                    layout: texture_atlas_layout.clone(),
-                    index: 0,
-                },
-                AnimationIndices {
-                    first: 0,
-                    last: 7,
-                    is_repeating: true,
-                },
-                AnimationTimer(Timer::from_seconds(0.1, TimerMode::Repeating)),
-                Sensor,
-                Collider::ball(28.0 / 2.0),
-                ProjectileBundleCollider::default(),
-            ))
+                    TextureAtlas {
+                        layout: sheet.layout.clone(),
+                        index: 0,
+                    },
+                    SpriteAnimation::new(sheet, "burn"),
+                    Sensor,
+                    Collider::ball(28.0 / 2.0),
+                    ProjectileBundleCollider::default(),
+                ),
+            )
             .insert((
                 Projectile,
-                ProjectileType(ProjectileTypes::FireBoots),
                 FireBoot,
                 ProjectileDamage(30.0),
                 ProjectileImpulse(20.0),
@@ -125,3 +122,24 @@ fn spawn_attack(
             ));
     }
 }
+
+fn apply_burn_on_hit(
+    enemies: Query<Entity, With<Enemy>>,
+    mut eneny_hit_event: EventReader<OnEnemyHit>,
+    mut apply_status_effect: EventWriter<OnApplyStatusEffect>,
+    player_stats: Res<PlayerInGameStats>,
+) {
+    for event in eneny_hit_event.read() {
+        if event.projectile_type != ProjectileTypes::FireBoots {
+            continue;
+        }
+        if let Ok(enemy_entity) = enemies.get(event.enemy_entity) {
+            apply_status_effect.send(OnApplyStatusEffect {
+                enemy_entity,
+                effect_type: StatusEffectTypes::Burn,
+                value: 8.0 * player_stats.power,
+                duration: 3.0,
+            });
+        }
+    }
+}
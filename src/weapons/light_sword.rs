@@ -1,5 +1,7 @@
 use crate::components::*;
 use crate::constants::PROJECTILE_Z_INDEX;
+use crate::math_utils::sprite_flip_and_rotation;
+use crate::pools::pool::ProjectileCommands;
 use bevy::prelude::*;
 use bevy_rapier2d::prelude::*;
 use rand::Rng;
@@ -22,7 +24,7 @@ impl Plugin for LightSwordsPlugin {
         );
         app.add_systems(
             Update,
-            (spawn_attack,).run_if(in_state(GameState::Gameplay)),
+            (spawn_attack, apply_vulnerability_on_hit).run_if(in_state(GameState::Gameplay)),
         );
     }
 }
@@ -52,7 +54,7 @@ fn spawn_weapon(mut commands: Commands, player_stats: Res<PlayerInGameStats>) {
 }
 
 fn spawn_attack(
-    mut commands: Commands,
+    mut commands: ProjectileCommands,
     asset_server: Res<AssetServer>,
     mut player: Query<(&Transform, &Player)>,
     mut spawner: Query<
@@ -77,38 +79,39 @@ fn spawn_attack(
         attack_ammo.amount -= 1;
         commands.entity(spawner_entity).remove::<CanAttack>();
 
-        let direction: Vec2 = match player.facing {
-            Facing::Left => Vec2::new(1.0, 0.0),
-            Facing::Right => Vec2::new(-1.0, 0.0),
-        };
+        // projectile directions are subtracted, swords fly toward the aim
+        let direction = -player.aim_direction();
+        let (is_flip, rotation) = sprite_flip_and_rotation(player.aim_direction());
 
         commands
-            .spawn((
-                SpriteBundle {
-                    texture: texture.clone(),
-                    transform: Transform {
-                        translation: Vec3::new(
-                            player_transform.translation.x,
-                            player_transform.translation.y
-                                + rand::thread_rng().gen_range(-10.0..10.0),
-                            PROJECTILE_Z_INDEX,
-                        ),
-                        scale: Vec3::splat(player_stats.area),
+            .spawn_projectile(
+                ProjectileTypes::LightSwords,
+                (
+                    SpriteBundle {
+                        texture: texture.clone(),
+                        transform: Transform {
+                            translation: Vec3::new(
+                                player_transform.translation.x,
+                                player_transform.translation.y
+                                    + rand::thread_rng().gen_range(-10.0..10.0),
+                                PROJECTILE_Z_INDEX,
+                            ),
+                            rotation,
+                            scale: Vec3::splat(player_stats.area),
+                        },
+                        sprite: Sprite {
+                            flip_x: is_flip,
+                            ..default()
+                        },
                         ..default()
                     },
-                    sprite: Sprite {
-                        flip_x: direction.x > 0.,
-                        ..default()
-                    },
-                    ..default()
-                },
-                Sensor,
-                Collider::capsule_x(12.0, 15.0 / 2.0),
-                ProjectileBundleCollider::default(),
-            ))
+                    Sensor,
+                    Collider::capsule_x(12.0, 15.0 / 2.0),
+                    ProjectileBundleCollider::default(),
+                ),
+            )
             .insert((
                 Projectile,
-                ProjectileType(ProjectileTypes::LightSwords),
                 LightSwords,
                 ProjectileDamage(40.0),
                 ProjectileImpulse(120.0),
@@ -126,3 +129,24 @@ fn spawn_attack(
             ));
     }
 }
+
+// each stack makes the enemy take 10% more damage
+fn apply_vulnerability_on_hit(
+    enemies: Query<Entity, With<Enemy>>,
+    mut eneny_hit_event: EventReader<OnEnemyHit>,
+    mut apply_status_effect: EventWriter<OnApplyStatusEffect>,
+) {
+    for event in eneny_hit_event.read() {
+        if event.projectile_type != ProjectileTypes::LightSwords {
+            continue;
+        }
+        if let Ok(enemy_entity) = enemies.get(event.enemy_entity) {
+            apply_status_effect.send(OnApplyStatusEffect {
+                enemy_entity,
+                effect_type: StatusEffectTypes::Vulnerability,
+                value: 0.1,
+                duration: 4.0,
+            });
+        }
+    }
+}
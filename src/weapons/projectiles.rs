@@ -1,8 +1,8 @@
 use crate::components::*;
 use crate::enemies::enemy::enemy_death_check;
 use crate::math_utils::simple_bezier;
+use crate::pools::pool::ProjectileCommands;
 use bevy::app::{App, Plugin, Update};
-use bevy::hierarchy::DespawnRecursiveExt;
 use bevy::prelude::*;
 use bevy_rapier2d::prelude::*;
 use std::f32::consts::{PI, TAU};
@@ -38,11 +38,11 @@ impl Plugin for ProjectilePlugin {
 }
 
 fn projectile_delete(
-    mut commands: Commands,
-    projectiles: Query<Entity, (With<Projectile>, With<ProjectileDeleteMe>)>,
+    mut commands: ProjectileCommands,
+    projectiles: Query<(Entity, &ProjectileType), With<ProjectileDeleteMe>>,
 ) {
-    for projectile_entity in &projectiles {
-        commands.entity(projectile_entity).despawn_recursive();
+    for (projectile_entity, projectile_type) in &projectiles {
+        commands.release_projectile(projectile_entity, projectile_type.0);
     }
 }
 
@@ -76,7 +76,9 @@ fn handle_projectile_colliding_with_enemy(
         ),
         (With<Projectile>, Without<ColliderDisabled>),
     >,
+    props: Query<(), With<Prop>>,
     mut eneny_hit_event: EventWriter<OnEnemyHit>,
+    mut prop_hit_event: EventWriter<OnPropHit>,
     player_stats: Res<PlayerInGameStats>,
     time: Res<Time>,
 ) {
@@ -111,13 +113,22 @@ fn handle_projectile_colliding_with_enemy(
                 }
                 hit_enemies.seen.push(enemy_entity);
             }
-            eneny_hit_event.send(OnEnemyHit {
-                enemy_entity,
-                damage: projectile_damage.0 * player_stats.power,
-                projectile_position: projectile_transform.translation,
-                projectile_type: projectile_type.0,
-                impulse: projectile_impulse.map(|projectile_impulse| projectile_impulse.0),
-            });
+
+            // props share the projectile collision path but don't trigger on hit effects
+            if props.contains(enemy_entity) {
+                prop_hit_event.send(OnPropHit {
+                    prop_entity: enemy_entity,
+                    damage: projectile_damage.0 * player_stats.power,
+                });
+            } else {
+                eneny_hit_event.send(OnEnemyHit {
+                    enemy_entity,
+                    damage: projectile_damage.0 * player_stats.power,
+                    projectile_position: projectile_transform.translation,
+                    projectile_type: projectile_type.0,
+                    impulse: projectile_impulse.map(|projectile_impulse| projectile_impulse.0),
+                });
+            }
 
             // if let Some(trigger) = triggers_on_hit{
             //     for aura_system in trigger.auras_systems.iter() {
@@ -283,7 +294,9 @@ fn projectile_move_toward_target_in_arc(
             transform.rotation = Quat::from_rotation_z(direction.to_angle() - PI)
         } else {
             // delete projectile
-            commands.entity(arcane_missile_entity).despawn_recursive();
+            commands
+                .entity(arcane_missile_entity)
+                .insert(ProjectileDeleteMe);
         }
     }
 }
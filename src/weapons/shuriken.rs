@@ -1,5 +1,6 @@
 use crate::components::*;
 use crate::constants::PROJECTILE_Z_INDEX;
+use crate::pools::pool::ProjectileCommands;
 use bevy::prelude::*;
 use bevy_rapier2d::prelude::*;
 use std::f32::consts::TAU;
@@ -81,7 +82,7 @@ fn handle_shuriken_extra_ammo_update(mut spawner: Query<&mut AttackAmmo, With<Sh
 }
 
 fn spawn_shuriken_attack(
-    mut commands: Commands,
+    mut commands: ProjectileCommands,
     asset_server: Res<AssetServer>,
     mut player: Query<&Transform, With<Player>>,
     mut spawner: Query<&mut AttackAmmo, (With<ShurikenSpawner>, Without<AttackSpawnerIsReloading>)>,
@@ -115,27 +116,29 @@ fn spawn_shuriken_attack(
             }
 
             let projectile_id = commands
-                .spawn((
-                    SpriteBundle {
-                        texture: texture.clone(),
-                        transform: Transform {
-                            translation: Vec3::new(
-                                player_transform.translation.x,
-                                player_transform.translation.y,
-                                PROJECTILE_Z_INDEX,
-                            ),
-                            scale: scale,
+                .spawn_projectile(
+                    ProjectileTypes::Shuriken,
+                    (
+                        SpriteBundle {
+                            texture: texture.clone(),
+                            transform: Transform {
+                                translation: Vec3::new(
+                                    player_transform.translation.x,
+                                    player_transform.translation.y,
+                                    PROJECTILE_Z_INDEX,
+                                ),
+                                scale: scale,
+                                ..default()
+                            },
                             ..default()
                         },
-                        ..default()
-                    },
-                    Sensor,
-                    Collider::ball(32.0 / 2.0),
-                    ProjectileBundleCollider::default(),
-                ))
+                        Sensor,
+                        Collider::ball(32.0 / 2.0),
+                        ProjectileBundleCollider::default(),
+                    ),
+                )
                 .insert((
                     Projectile,
-                    ProjectileType(ProjectileTypes::Shuriken),
                     Shuriken,
                     ProjectileSpeed(150.0),
                     ProjectileDirection(direction),
@@ -184,7 +187,7 @@ fn spawn_shuriken_attack(
 }
 
 fn handle_mini_shuriken_on_hit(
-    mut commands: Commands,
+    mut commands: ProjectileCommands,
     asset_server: Res<AssetServer>,
     mut eneny_hit_event: EventReader<OnEnemyHit>,
     player_stats: Res<PlayerInGameStats>,
@@ -206,27 +209,29 @@ fn handle_mini_shuriken_on_hit(
         }
 
         let _projectile_id = commands
-            .spawn((
-                SpriteBundle {
-                    texture,
-                    transform: Transform {
-                        translation: Vec3::new(
-                            event.projectile_position.x,
-                            event.projectile_position.y,
-                            PROJECTILE_Z_INDEX,
-                        ),
-                        scale: scale,
+            .spawn_projectile(
+                ProjectileTypes::ShurikenMini,
+                (
+                    SpriteBundle {
+                        texture,
+                        transform: Transform {
+                            translation: Vec3::new(
+                                event.projectile_position.x,
+                                event.projectile_position.y,
+                                PROJECTILE_Z_INDEX,
+                            ),
+                            scale: scale,
+                            ..default()
+                        },
                         ..default()
                     },
-                    ..default()
-                },
-                Sensor,
-                Collider::ball(32.0 / 2.0),
-            ))
+                    Sensor,
+                    Collider::ball(32.0 / 2.0),
+                ),
+            )
             .insert((
                 Projectile,
                 ProjectileBundleCollider::default(),
-                ProjectileType(ProjectileTypes::ShurikenMini),
                 Shuriken,
                 ProjectileDamage(10.0),
                 ProjectileImpulse(3000.0),
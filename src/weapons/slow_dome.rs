@@ -1,6 +1,7 @@
 use crate::components::*;
 use crate::constants::PROJECTILE_Z_INDEX;
 use crate::math_utils::find_closest;
+use crate::pools::pool::ProjectileCommands;
 use bevy::prelude::*;
 use bevy_rapier2d::prelude::*;
 
@@ -55,7 +56,7 @@ fn spawn_weapon(mut commands: Commands, player_stats: Res<PlayerInGameStats>) {
 }
 
 fn spawn_attack(
-    mut commands: Commands,
+    mut commands: ProjectileCommands,
     asset_server: Res<AssetServer>,
     mut player: Query<&Transform, With<Player>>,
     mut spawner: Query<
@@ -86,70 +87,64 @@ fn spawn_attack(
                 commands.entity(spawner_entity).remove::<CanAttack>();
 
                 let texture = asset_server.load("slow-dome.png");
-                commands.spawn((
-                    SpriteBundle {
-                        texture,
-                        transform: Transform {
-                            translation: Vec3::new(
-                                enemy_transform.translation.x,
-                                enemy_transform.translation.y,
-                                PROJECTILE_Z_INDEX,
-                            ),
-                            scale: Vec3::splat(player_stats.area),
+                commands.spawn_projectile(
+                    ProjectileTypes::SlowDome,
+                    (
+                        SpriteBundle {
+                            texture,
+                            transform: Transform {
+                                translation: Vec3::new(
+                                    enemy_transform.translation.x,
+                                    enemy_transform.translation.y,
+                                    PROJECTILE_Z_INDEX,
+                                ),
+                                scale: Vec3::splat(player_stats.area),
+                                ..default()
+                            },
                             ..default()
                         },
-                        ..default()
-                    },
-                    Sensor,
-                    Collider::ball(95.0 / 2.0),
-                    ProjectileBundleCollider::default(),
-                    ProjectileLifetime {
-                        timer: Timer::from_seconds(
-                            8.0 * player_stats.attack_duration,
-                            TimerMode::Once,
-                        ),
-                    },
-                    ProjectileDamage(15.0),
-                    ProjectileTimeBetweenDamage {
-                        timer: Timer::from_seconds(0.33, TimerMode::Repeating),
-                    },
-                    SlowDome,
-                    Projectile,
-                    ProjectilePierce,
-                    ProjectileType(ProjectileTypes::SlowDome),
-                    // TriggersOnHit{
-                    //     auras_systems: vec![systems.slow_enemy]
-                    // },
-                    Name::new("Slow dome Attack"),
-                ));
+                        Sensor,
+                        Collider::ball(95.0 / 2.0),
+                        ProjectileBundleCollider::default(),
+                        ProjectileLifetime {
+                            timer: Timer::from_seconds(
+                                8.0 * player_stats.attack_duration,
+                                TimerMode::Once,
+                            ),
+                        },
+                        ProjectileDamage(15.0),
+                        ProjectileTimeBetweenDamage {
+                            timer: Timer::from_seconds(0.33, TimerMode::Repeating),
+                        },
+                        SlowDome,
+                        Projectile,
+                        ProjectilePierce,
+                        // TriggersOnHit{
+                        //     auras_systems: vec![systems.slow_enemy]
+                        // },
+                        Name::new("Slow dome Attack"),
+                    ),
+                );
             }
         }
     }
 }
 
-// fn apply_slow_on_hit(
-//     In(payload): In<PayloadOnHit>,
-//     mut commands: Commands,
-// ){
-//     // commands.entity(payload.target).insert(VelocityAura {
-//     //     value: 0.5,
-//     //     lifetime: Timer::from_seconds(2.0, TimerMode::Once),
-//     // },);
-// }
-
 fn apply_slow_aura_on_hit(
-    mut commands: Commands,
     enemies: Query<Entity, With<Enemy>>,
     mut eneny_hit_event: EventReader<OnEnemyHit>,
+    mut apply_status_effect: EventWriter<OnApplyStatusEffect>,
 ) {
     for event in eneny_hit_event.read() {
         if event.projectile_type != ProjectileTypes::SlowDome {
             continue;
         }
         if let Ok(enemy_entity) = enemies.get(event.enemy_entity) {
-            commands.entity(enemy_entity).try_insert(VelocityAura {
+            apply_status_effect.send(OnApplyStatusEffect {
+                enemy_entity,
+                effect_type: StatusEffectTypes::Slow,
                 value: 0.5,
-                lifetime: Timer::from_seconds(2.0, TimerMode::Once),
+                duration: 2.0,
             });
         }
     }